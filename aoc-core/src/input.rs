@@ -0,0 +1,103 @@
+use std::{
+    env, fs,
+    io::{self, ErrorKind, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+/// Environment variable overriding the directory searched for `day<N>.txt`
+pub const INPUT_DIR_VAR: &str = "AOC_INPUTS";
+
+/// Where a day's puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    /// Looks for `day<N>.txt` inside the directory
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    /// `-` reads from stdin and any other argument is treated as a path. With
+    /// no argument the per-day inputs directory is used.
+    pub fn from_arg(arg: Option<&str>) -> Self {
+        match arg {
+            Some("-") => Self::Stdin,
+            Some(path) => Self::File(PathBuf::from(path)),
+            None => Self::Directory(default_dir()),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String> {
+        match self {
+            Self::File(path) => read_file(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Couldn't read input from stdin")?;
+                Ok(input)
+            }
+            Self::Directory(dir) => {
+                let path = dir.join(format!("day{day}.txt"));
+                read_file(&path).map_err(|e| {
+                    anyhow!(
+                        "{e}\nSave the puzzle input for day {day} there, pass a path, or use '-' to read from stdin"
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// `$AOC_INPUTS` if set, otherwise `inputs` in the current directory
+pub fn default_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or_else(|| PathBuf::from("inputs"), PathBuf::from)
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => anyhow!("Input file {} does not exist", path.display()),
+        _ => anyhow!(e).context(format!("Couldn't read input {}", path.display())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("day1.txt")),
+            InputSource::File(PathBuf::from("day1.txt"))
+        );
+    }
+
+    #[test]
+    fn read_directory() {
+        let dir = env::temp_dir().join("aoc-core-read-directory");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day3.txt"), "467..114..").unwrap();
+
+        let source = InputSource::Directory(dir.clone());
+        assert_eq!(source.read(3).unwrap(), "467..114..");
+
+        let err = source.read(4).unwrap_err().to_string();
+        assert!(err.contains("day4.txt"), "{err}");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = InputSource::File(PathBuf::from("does/not/exist.txt"))
+            .read(1)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Input file does/not/exist.txt does not exist"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::{env, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+pub use input::InputSource;
+
+mod input;
+
+/// A single day's puzzle. Answers are returned as strings so that days with
+/// differing answer types can be run side by side.
+pub trait Solution {
+    fn day(&self) -> u8;
+    fn part_one(&self, input: &str) -> Result<String>;
+    fn part_two(&self, input: &str) -> Result<String>;
+
+    fn run(&self, part: Part, input: &str) -> Result<String> {
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Self::One),
+            "2" | "two" => Ok(Self::Two),
+            p => Err(anyhow!("Unknown part {p}, expected 1 or 2")),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => write!(f, "Part one"),
+            Self::Two => write!(f, "Part two"),
+        }
+    }
+}
+
+/// Runs both parts of a solution, printing each answer as it is found
+pub fn print_solution(solution: &dyn Solution, input: &str) -> Result<()> {
+    for part in Part::ALL {
+        println!("{part}: {}", solution.run(part, input)?);
+    }
+    Ok(())
+}
+
+/// Entry point for the per-day binaries. The first argument picks the input
+/// source, see [`InputSource::from_arg`].
+pub fn solve_from_args(solution: &dyn Solution) -> Result<()> {
+    let arg = env::args().nth(1);
+    let input = InputSource::from_arg(arg.as_deref()).read(solution.day())?;
+    print_solution(solution, &input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        fn day(&self) -> u8 {
+            0
+        }
+        fn part_one(&self, input: &str) -> Result<String> {
+            Ok(input.to_owned())
+        }
+        fn part_two(&self, input: &str) -> Result<String> {
+            Ok(input.len().to_string())
+        }
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("two".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn run_part() {
+        assert_eq!(Echo.run(Part::One, "abc").unwrap(), "abc");
+        assert_eq!(Echo.run(Part::Two, "abc").unwrap(), "3");
+    }
+}
@@ -0,0 +1,358 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Result};
+
+/// (x, y) position in a grid, with y increasing downwards
+pub type Position = (usize, usize);
+
+/// A rectangular grid stored row by row in a single buffer
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Box<[T]>,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: impl Into<Box<[T]>>, width: usize) -> Result<Self> {
+        let cells = cells.into();
+        if width == 0 && !cells.is_empty() || width != 0 && cells.len() % width != 0 {
+            return Err(anyhow!(
+                "Can't split {} cells into rows of {width}",
+                cells.len()
+            ));
+        }
+        Ok(Self { cells, width })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height].into(),
+            width,
+        }
+    }
+
+    /// Parses one row per line, converting each character with `f`
+    pub fn parse_with(s: &str, mut f: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let width = s.lines().next().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(s.len());
+        for l in s.lines() {
+            let start = cells.len();
+            for c in l.chars() {
+                cells.push(f(c)?);
+            }
+            if cells.len() - start != width {
+                return Err(anyhow!("Lines in input with differing lengths."));
+            }
+        }
+        Ok(Self {
+            cells: cells.into(),
+            width,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains((x, y))
+            .then(|| &self.cells[y * self.width + x])
+    }
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.contains((x, y))
+            .then(|| &mut self.cells[y * self.width + x])
+    }
+    /// Returns false if the position is outside of the grid
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.get_mut(x, y) {
+            Some(cell) => *cell = value,
+            None => return false,
+        }
+        true
+    }
+
+    /// Moves `(dx, dy)` from `pos`, if that stays inside the grid
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The orthogonal neighbours of `pos` that are inside the grid, in the
+    /// order up, down, left, right
+    pub fn neighbours(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        [(0, -1), (0, 1), (-1, 0), (1, 0)]
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Cells in row order, alongside their positions
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i % width, i / width), c))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height()).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // chunks_exact panics on a zero width, but then there are no cells anyway
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl DoubleEndedIterator<Item = &T>> {
+        (x < self.width).then(|| self.column_cells(x))
+    }
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column_cells(x))
+    }
+    fn column_cells(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        // A grid with no rows has no cells to start a column from
+        self.cells
+            .get(x..)
+            .unwrap_or_default()
+            .iter()
+            .step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+        }
+    }
+
+    /// Swaps rows and columns, so `(x, y)` moves to `(y, x)`
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self.columns().flatten().cloned().collect(),
+            width: self.height(),
+        }
+    }
+
+    /// Rotates a quarter turn, so the left column becomes the top row
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self.columns().flat_map(|c| c.rev()).cloned().collect(),
+            width: self.height(),
+        }
+    }
+
+    /// Rotates a quarter turn, so the right column becomes the top row
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: self.columns().rev().flatten().cloned().collect(),
+            width: self.height(),
+        }
+    }
+
+    /// Writes the grid back out as text, one line per row
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.cells.len() + self.height());
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.extend(row.iter().map(&mut f));
+        }
+        out
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Position) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Position) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    anyhow::Error: From<T::Error>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| Ok(T::try_from(c)?))
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Copy,
+    char: From<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|&c| char::from(c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Open,
+        Wall,
+    }
+
+    impl TryFrom<char> for Tile {
+        type Error = anyhow::Error;
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            match value {
+                '.' => Ok(Self::Open),
+                '#' => Ok(Self::Wall),
+                c => Err(anyhow!("Unknown tile {c}")),
+            }
+        }
+    }
+
+    impl From<Tile> for char {
+        fn from(value: Tile) -> Self {
+            match value {
+                Tile::Open => '.',
+                Tile::Wall => '#',
+            }
+        }
+    }
+
+    const INPUT: &str = {
+        "#..
+.#.
+..#
+##."
+    };
+
+    #[test]
+    fn parse_and_display() {
+        let grid: Grid<Tile> = INPUT.parse().unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid.get(1, 1), Some(&Tile::Wall));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid[(1, 3)], Tile::Wall);
+        assert_eq!(grid.to_string(), INPUT);
+
+        assert!("#..\n.#".parse::<Grid<Tile>>().is_err());
+        assert!("#x.".parse::<Grid<Tile>>().is_err());
+    }
+
+    #[test]
+    fn digits() {
+        let grid = Grid::parse_with("123\n456", |c| {
+            c.to_digit(10).ok_or(anyhow!("{c} isn't a digit"))
+        })
+        .unwrap();
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(
+            grid.render(|d| char::from_digit(*d, 10).unwrap()),
+            "123\n456"
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::filled(3, 2, 0);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1)]
+        );
+        assert_eq!(grid.offset((2, 1), (1, 0)), None);
+        assert_eq!(grid.offset((2, 1), (-2, -1)), Some((0, 0)));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::new(vec![1, 2, 3, 4, 5, 6], 3).unwrap();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).unwrap().copied().collect::<Vec<_>>(), [2, 5]);
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.columns().count(), 3);
+        assert!(Grid::new(vec![1, 2, 3], 2).is_err());
+    }
+
+    #[test]
+    fn transform() {
+        // 1 2 3
+        // 4 5 6
+        let grid = Grid::new(vec![1, 2, 3, 4, 5, 6], 3).unwrap();
+        assert_eq!(
+            grid.transpose(),
+            Grid::new(vec![1, 4, 2, 5, 3, 6], 2).unwrap()
+        );
+        assert_eq!(
+            grid.rotate_clockwise(),
+            Grid::new(vec![4, 1, 5, 2, 6, 3], 2).unwrap()
+        );
+        assert_eq!(
+            grid.rotate_counter_clockwise(),
+            Grid::new(vec![3, 6, 2, 5, 1, 4], 2).unwrap()
+        );
+        let mut spun = grid.clone();
+        for _ in 0..4 {
+            spun = spun.rotate_clockwise();
+        }
+        assert_eq!(spun, grid);
+    }
+
+    #[test]
+    fn empty() {
+        let grid: Grid<Tile> = "".parse().unwrap();
+        assert_eq!(grid.height(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+
+        // Rows of 3 cells, but no rows
+        let grid = Grid::<u8>::new(Vec::new(), 3).unwrap();
+        assert_eq!(grid, Grid::filled(3, 0, 0));
+        assert_eq!(grid.height(), 0);
+        assert_eq!(grid.column(2).unwrap().count(), 0);
+        assert!(grid.columns().all(|mut c| c.next().is_none()));
+        assert_eq!(grid.transpose().height(), 0);
+        assert_eq!(grid.rotate_clockwise().height(), 0);
+        assert_eq!(grid.rotate_counter_clockwise().height(), 0);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+/// The cheapest route found from a start state to a goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    /// Every state visited, from the start to the goal inclusive
+    pub states: Vec<S>,
+}
+
+/// Finds the cheapest path from any of `starts` to a state matching
+/// `is_goal`. `successors` yields each state reachable in one step from the
+/// given state, along with the cost of that step.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    a_star(starts, successors, |_| 0, is_goal)
+}
+
+/// Like [`dijkstra`], but expands states in order of cost plus `heuristic`.
+/// The heuristic must never overestimate the remaining cost to a goal, or
+/// the path found may not be the cheapest.
+pub fn a_star<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    // States are stored once and referred to by index from then on
+    let mut states: Vec<S> = Vec::new();
+    let mut indices: HashMap<S, usize> = HashMap::new();
+    // (best known cost, previous state on that path)
+    let mut best: Vec<(usize, Option<usize>)> = Vec::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = indices.entry(start.clone()) {
+            e.insert(states.len());
+            queue.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+            best.push((0, None));
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        if cost > best[index].0 {
+            // Already expanded through a cheaper path
+            continue;
+        }
+        if is_goal(&states[index]) {
+            return Some(Path {
+                cost,
+                states: trace(&states, &best, index),
+            });
+        }
+
+        for (next, step) in successors(&states[index]) {
+            let next_cost = cost + step;
+            let next_index = match indices.entry(next) {
+                Entry::Occupied(e) => {
+                    let i = *e.get();
+                    if next_cost >= best[i].0 {
+                        continue;
+                    }
+                    best[i] = (next_cost, Some(index));
+                    i
+                }
+                Entry::Vacant(e) => {
+                    let i = states.len();
+                    states.push(e.key().clone());
+                    e.insert(i);
+                    best.push((next_cost, Some(index)));
+                    i
+                }
+            };
+            let estimate = next_cost + heuristic(&states[next_index]);
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+    None
+}
+
+fn trace<S: Clone>(states: &[S], best: &[(usize, Option<usize>)], goal: usize) -> Vec<S> {
+    let mut path = vec![states[goal].clone()];
+    let mut current = goal;
+    while let Some(prev) = best[current].1 {
+        path.push(states[prev].clone());
+        current = prev;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 costs 4, 0 -> 2 -> 1 costs 3
+    fn graph(node: &u8) -> Vec<(u8, usize)> {
+        match node {
+            0 => vec![(1, 4), (2, 1)],
+            2 => vec![(1, 2), (3, 7)],
+            1 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra([0], graph, |&n| n == 3).unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states, vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        assert!(dijkstra([0], graph, |&n| n == 4).is_none());
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let path = dijkstra([2, 0], graph, |&n| n == 0).unwrap();
+        assert_eq!(path.cost, 0);
+        assert_eq!(path.states, vec![0]);
+    }
+
+    #[test]
+    fn a_star_grid() {
+        // Walk a 10x10 open grid from the top left to the bottom right
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+                .map(|p| (p, 1))
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (9 - x + 9 - y) as usize;
+        let path = a_star([(0, 0)], successors, manhattan, |&p| p == (9, 9)).unwrap();
+        assert_eq!(path.cost, 18);
+        assert_eq!(path.states.len(), 19);
+        assert_eq!(
+            dijkstra([(0, 0)], successors, |&p| p == (9, 9))
+                .unwrap()
+                .cost,
+            18
+        );
+    }
+}
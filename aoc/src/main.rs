@@ -0,0 +1,151 @@
+use std::{env, time::Instant};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::{InputSource, Part, Solution};
+
+const USAGE: &str = "Usage:
+    aoc list
+    aoc run <day> [--part <1|2>] [--input <path|->]
+
+Without --input the puzzle input is read from $AOC_INPUTS/day<N>.txt, or
+inputs/day<N>.txt when AOC_INPUTS isn't set. An input of '-' reads stdin.";
+
+const SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+];
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    List,
+    Run {
+        day: u8,
+        part: Option<Part>,
+        input: InputSource,
+    },
+}
+
+impl Command {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        match args.next().as_deref() {
+            Some("list") => Ok(Self::List),
+            Some("run") => {
+                let day = args
+                    .next()
+                    .context("Missing day to run")?
+                    .parse()
+                    .context("Day must be a number")?;
+                let (mut part, mut input) = (None, None);
+                while let Some(flag) = args.next() {
+                    let value = args
+                        .next()
+                        .with_context(|| format!("Missing value for {flag}"))?;
+                    match flag.as_str() {
+                        "--part" | "-p" => part = Some(value.parse()?),
+                        "--input" | "-i" => input = Some(value),
+                        f => return Err(anyhow!("Unknown flag {f}")),
+                    }
+                }
+                Ok(Self::Run {
+                    day,
+                    part,
+                    input: InputSource::from_arg(input.as_deref()),
+                })
+            }
+            Some(c) => Err(anyhow!("Unknown command {c}")),
+            None => Err(anyhow!("Missing command")),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let command = Command::parse(env::args().skip(1)).map_err(|e| anyhow!("{e}\n\n{USAGE}"))?;
+
+    match command {
+        Command::List => {
+            for solution in SOLUTIONS {
+                println!("Day {}", solution.day());
+            }
+        }
+        Command::Run { day, part, input } => {
+            let solution = find_solution(day)?;
+            let input = input.read(day)?;
+            let parts = match part {
+                Some(p) => vec![p],
+                None => Part::ALL.to_vec(),
+            };
+            for part in parts {
+                let start = Instant::now();
+                let answer = solution.run(part, &input)?;
+                println!("{part}: {answer} ({:?})", start.elapsed());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn find_solution(day: u8) -> Result<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.day() == day)
+        .copied()
+        .ok_or(anyhow!("No solution for day {day}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(str::to_owned)
+    }
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(
+            Command::parse(args("run 17 --part 2 --input day17.txt")).unwrap(),
+            Command::Run {
+                day: 17,
+                part: Some(Part::Two),
+                input: InputSource::File("day17.txt".into())
+            }
+        );
+        assert_eq!(
+            Command::parse(args("run 17 -i -")).unwrap(),
+            Command::Run {
+                day: 17,
+                part: None,
+                input: InputSource::Stdin
+            }
+        );
+        assert!(Command::parse(args("run")).is_err());
+        assert!(Command::parse(args("run 17 --part 3 --input a")).is_err());
+    }
+
+    #[test]
+    fn solutions_in_order() {
+        for (i, s) in SOLUTIONS.iter().enumerate() {
+            assert_eq!(s.day() as usize, i + 1);
+        }
+        assert!(find_solution(17).is_ok());
+        assert!(find_solution(26).is_err());
+    }
+}
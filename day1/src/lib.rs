@@ -0,0 +1,61 @@
+use aoc_core::Solution;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> u32 {
+    input.trim().lines().fold(0, |acc, l| acc + calibrate(l))
+}
+
+fn calibrate(line: &str) -> u32 {
+    let mut chars = line.chars();
+    let first = chars
+        .find(|c| c.is_ascii_digit())
+        .expect("at least one number should exist in the input");
+    let last = chars.rev().find(|c| c.is_ascii_digit()).unwrap_or(first);
+    first.to_digit(10).unwrap() * 10 + last.to_digit(10).unwrap()
+}
+
+pub fn part_two(input: &str) -> usize {
+    input
+        .trim()
+        .lines()
+        .fold(0, |acc, l| acc + calibrate_fixed(l))
+}
+
+fn calibrate_fixed(line: &str) -> usize {
+    let numbers = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1",
+        "2", "3", "4", "5", "6", "7", "8", "9",
+    ];
+    let (mut min_start, mut min_val) = (usize::MAX, 0);
+    let (mut max_start, mut max_val) = (0, 0);
+
+    for (i, num) in numbers.into_iter().enumerate() {
+        if let Some(ind) = line.find(num) {
+            if ind < min_start {
+                min_start = ind;
+                min_val = i % 10;
+            }
+
+            if let Some(ind) = line.rfind(num) {
+                if ind >= max_start {
+                    max_start = ind;
+                    max_val = i % 10;
+                }
+            }
+        }
+    }
+    min_val * 10 + max_val
+}
@@ -0,0 +1,237 @@
+use aoc_core::Solution;
+use aoc_grid::Grid;
+
+use pipe::Pipe;
+
+mod pipe;
+
+pub type Point = (usize, usize);
+pub type Map = Grid<Option<Pipe>>;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> u32 {
+    let (map, start) = parse_map(input);
+
+    let mut visited = Grid::filled(map.width(), map.height(), false);
+    visited[start] = true;
+
+    let [mut left, mut right] = map[start].as_ref().unwrap().connections(start);
+    let mut steps = 1;
+
+    while left != right && !visited[left] {
+        visited[left] = true;
+        visited[right] = true;
+        left = map[left]
+            .as_ref()
+            .unwrap()
+            .connections(left)
+            .into_iter()
+            .find(|p| !visited[*p] || p == &right)
+            .unwrap();
+        right = map[right]
+            .as_ref()
+            .unwrap()
+            .connections(right)
+            .into_iter()
+            .find(|p| !visited[*p] || left == right) // since left already moved
+            .unwrap();
+        steps += 1;
+    }
+    steps
+}
+
+pub fn part_two(input: &str) -> u32 {
+    let (map, start) = parse_map(input);
+
+    let mut on_loop = Grid::filled(map.width(), map.height(), false);
+    on_loop[start] = true;
+
+    let [mut left, finish] = map[start].as_ref().unwrap().connections(start);
+    on_loop[finish] = true;
+
+    while left != finish {
+        on_loop[left] = true;
+        left = map[left]
+            .as_ref()
+            .unwrap()
+            .connections(left)
+            .into_iter()
+            .find(|p| !on_loop[*p] || p == &finish) // immediately returning to start
+            .unwrap();
+    }
+
+    let mut count = 0;
+
+    for (y, row) in map.rows().enumerate() {
+        let mut state = false;
+        let mut start_corner = None;
+        for (x, pipe) in row.iter().enumerate() {
+            // Going east to west
+            if on_loop[(x, y)] {
+                let pipe = pipe.as_ref().unwrap();
+                match pipe {
+                    Pipe::Ns => state = !state,
+                    Pipe::Ew => (),
+                    // L
+                    Pipe::Ne => match start_corner {
+                        Some(_) => unreachable!(),
+                        None => start_corner = Some(Pipe::Ne),
+                    },
+                    Pipe::Nw => match start_corner {
+                        // L--J
+                        Some(Pipe::Ne) => start_corner = None,
+                        // F--J
+                        Some(Pipe::Se) => {
+                            state = !state;
+                            start_corner = None
+                        }
+                        _ => unreachable!(),
+                    },
+                    // 7
+                    Pipe::Sw => match start_corner {
+                        // L--7
+                        Some(Pipe::Ne) => {
+                            state = !state;
+                            start_corner = None;
+                        }
+                        // F--7
+                        Some(Pipe::Se) => start_corner = None,
+                        _ => unreachable!(),
+                    },
+                    Pipe::Se => match start_corner {
+                        Some(_) => unreachable!(),
+                        None => start_corner = Some(Pipe::Se),
+                    },
+                };
+            } else if state {
+                count += 1;
+            }
+        }
+        assert!(
+            start_corner.is_none(),
+            "Mismatched corner {:?} on line {}",
+            start_corner,
+            y
+        );
+        assert!(
+            !state,
+            "Expected to be out of the loop at the end of line {}",
+            y
+        )
+    }
+
+    count
+}
+
+fn parse_map(input: &str) -> (Map, Point) {
+    let start = input
+        .lines()
+        .enumerate()
+        .find_map(|(y, l)| l.find('S').map(|x| (x, y)))
+        .expect("Map should have a starting point");
+    // 'S' and '.' are both empty until the starting pipe is identified
+    let mut map: Map = Grid::parse_with(input, |c| Ok(Pipe::try_from(c).ok()))
+        .expect("All lines should be the same length");
+
+    // Identify the correct pipe type for the starting point
+    let north = start.1 > 0
+        && map[(start.0, start.1 - 1)]
+            .as_ref()
+            .is_some_and(|p| p.connections((start.0, start.1 - 1)).contains(&start));
+    let south = map[(start.0, start.1 + 1)]
+        .as_ref()
+        .is_some_and(|p| p.connections((start.0, start.1 + 1)).contains(&start));
+    let east = map[(start.0 + 1, start.1)]
+        .as_ref()
+        .is_some_and(|p| p.connections((start.0 + 1, start.1)).contains(&start));
+    let west = start.0 > 0
+        && map[(start.0 - 1, start.1)]
+            .as_ref()
+            .is_some_and(|p| p.connections((start.0 - 1, start.1)).contains(&start));
+    match (north, south, east, west) {
+        (true, true, _, _) => map[start] = Some(Pipe::Ns),
+        (true, _, true, _) => map[start] = Some(Pipe::Ne),
+        (true, _, _, true) => map[start] = Some(Pipe::Nw),
+        (_, _, true, true) => map[start] = Some(Pipe::Ew),
+        (_, true, true, _) => map[start] = Some(Pipe::Se),
+        (_, true, _, true) => map[start] = Some(Pipe::Sw),
+        _ => unreachable!(),
+    }
+
+    (map, start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE: &str = {
+        ".....
+.S-7.
+.|.|.
+.L-J.
+....."
+    };
+    const RH_SIMPLE: &str = {
+        "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF"
+    };
+
+    const COMPLEX: &str = {
+        "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ..."
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(SIMPLE), 4);
+        assert_eq!(part_one(RH_SIMPLE), 4);
+    }
+
+    #[test]
+    fn part_one_complex() {
+        assert_eq!(part_one(COMPLEX), 8);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(SIMPLE), 1);
+        assert_eq!(part_two(RH_SIMPLE), 1);
+    }
+
+    #[test]
+    fn part_two_med() {
+        let input = {
+            "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+..........."
+        };
+
+        assert_eq!(part_two(input), 4);
+    }
+}
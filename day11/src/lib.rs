@@ -0,0 +1,54 @@
+use aoc_core::Solution;
+
+mod observation;
+
+use observation::Observation;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> usize {
+    let mut obs = input.parse::<Observation>().unwrap();
+    obs.expand(2);
+    obs.shortest_paths()
+}
+
+pub fn part_two(input: &str) -> usize {
+    let mut obs = input.parse::<Observation>().unwrap();
+    obs.expand(1_000_000);
+    obs.shortest_paths()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = {
+        "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#....."
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT), 374);
+    }
+}
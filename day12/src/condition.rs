@@ -1,9 +1,10 @@
-use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+use std::{collections::HashMap, iter, num::ParseIntError, str::FromStr};
 
 use anyhow::anyhow;
+use rand::{Rng, RngExt};
 
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
-enum Condition {
+pub enum Condition {
     Good,
     Bad,
     #[default]
@@ -24,7 +25,6 @@ impl TryFrom<char> for Condition {
 }
 
 impl Condition {
-    #[allow(unused)]
     pub fn row_string(slice: &[Condition]) -> String {
         slice
             .iter()
@@ -37,77 +37,249 @@ impl Condition {
     }
 }
 
-fn condition_match(base: &[Condition], test: &[Condition]) -> bool {
-    assert_eq!(base.len(), test.len());
-    for (b, t) in base.iter().zip(test.iter()) {
-        if *b != Condition::Unknown && b != t {
-            return false;
-        }
-    }
-    true
-}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RowCondition {
     conditions: Box<[Condition]>,
     sequences: Box<[usize]>,
 }
 
+/// Where counting has got to between two springs: the number of groups
+/// finished, and how many damaged springs into the next group it is
+type State = (usize, usize);
+
+/// For each state a copy of a row could start in, by the index of its group
+/// in the row's groups, the states it could end in and the number of ways to
+/// get to each. Groups finished are counted from the start of the copy.
+type Transfer = HashMap<State, Vec<(State, u128)>>;
+
+/// The table behind counting, indexing and sampling arrangements
+struct Counts {
+    /// How many springs from each position on could all be damaged
+    open: Box<[usize]>,
+    /// `ways[group][pos]` is the number of ways to place the groups from
+    /// `group` onwards in the springs from `pos` onwards
+    ways: Vec<Vec<u128>>,
+}
+
+impl Counts {
+    fn total(&self) -> u128 {
+        self.ways[0][0]
+    }
+}
+
 impl RowCondition {
-    pub fn possible_combinations(&self) -> u64 {
-        let mut combo = vec![Condition::Good; self.conditions.len()];
+    /// The number of ways to fill in the unknown springs so the damaged ones
+    /// make up the row's groups. Fails if there are too many to count.
+    pub fn possible_combinations(&self) -> anyhow::Result<u128> {
+        Ok(self.counts()?.total())
+    }
+
+    /// Every arrangement of the row, with the unknown springs filled in.
+    /// Arrangements come in the same order as `arrangement` numbers them.
+    pub fn arrangements(&self) -> anyhow::Result<impl Iterator<Item = String> + '_> {
+        let counts = self.counts()?;
+        Ok((0..counts.total()).map(move |index| self.unrank(&counts, index)))
+    }
 
-        let mut cache = HashMap::new();
-        self.combo_rec(&mut cache, &mut combo, 0, &self.sequences)
+    /// The arrangement at `index`, counting from the one with every group as
+    /// far left as it can go. None if there are fewer than `index + 1`.
+    pub fn arrangement(&self, index: u128) -> anyhow::Result<Option<String>> {
+        let counts = self.counts()?;
+        Ok((index < counts.total()).then(|| self.unrank(&counts, index)))
+    }
+
+    /// An arrangement picked uniformly at random from all of them, or None
+    /// if the row can't be arranged at all
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> anyhow::Result<Option<String>> {
+        let counts = self.counts()?;
+        let total = counts.total();
+        Ok((total > 0).then(|| self.unrank(&counts, rng.random_range(0..total))))
+    }
+
+    /// The row repeated `folds` times, with a `separator` spring between
+    /// each copy, and the groups repeated to match
+    pub fn unfold(&self, folds: usize, separator: Condition) -> Self {
+        let copies = vec![&self.conditions[..]; folds];
+        Self {
+            conditions: copies.join(&separator).into(),
+            sequences: self.sequences.repeat(folds).into(),
+        }
     }
 
-    fn combo_rec(
+    /// The same as counting the arrangements of `unfold(folds, separator)`,
+    /// but without building the unfolded row. Counts are carried from one
+    /// copy to the next through the same transfer, keyed by how many groups
+    /// ahead of or behind the copies so far counting is, which also says
+    /// which of the row's groups comes next. States too far out of step to
+    /// catch up by the last copy are dropped, so a copy only costs as much as
+    /// the ways the row can drift out of step.
+    pub fn unfolded_combinations(
         &self,
-        cache: &mut HashMap<(usize, usize), u64>,
-        combo: &mut [Condition],
-        filled: usize,
-        sequence: &[usize],
-    ) -> u64 {
-        if sequence.is_empty() || filled >= combo.len() {
-            // Check the rest to see if all good is ok
-            if condition_match(
-                &self.conditions[filled.min(combo.len())..],
-                &combo[filled.min(combo.len())..],
-            ) {
-                return 1;
-            } else {
-                return 0;
+        folds: usize,
+        separator: Condition,
+    ) -> anyhow::Result<u128> {
+        if folds == 0 || self.sequences.is_empty() {
+            return self.unfold(folds, separator).possible_combinations();
+        }
+        let k = self.sequences.len() as isize;
+        let first = self.transfer(&self.conditions)?;
+        let rest = self.transfer(&[&[separator], &self.conditions[..]].concat())?;
+        let finished = rest.values().flatten().map(|&((f, _), _)| f as isize);
+        let (least, most) = finished.fold((k, k), |(l, m), f| (l.min(f), m.max(f)));
+
+        // Groups finished beyond `k` per copy so far, and the run into the next
+        let mut ways: HashMap<(isize, usize), u128> = HashMap::from([((0, 0), 1)]);
+        for (copy, transfer) in iter::once(&first)
+            .chain(iter::repeat_n(&rest, folds - 1))
+            .enumerate()
+        {
+            let left = (folds - copy - 1) as isize;
+            let mut next = HashMap::new();
+            for ((ahead, run), count) in ways {
+                let start = (ahead.rem_euclid(k) as usize, run);
+                for &((finished, end_run), through) in &transfer[&start] {
+                    let ahead = ahead + finished as isize - k;
+                    // Every copy left finishing as few or as many groups as
+                    // it can still has to end up at 0, or -1 part way
+                    // through the last group
+                    if ahead + left * (least - k) > 0 || ahead + left * (most - k) < -1 {
+                        continue;
+                    }
+                    let total: &mut u128 = next.entry((ahead, end_run)).or_default();
+                    *total = count
+                        .checked_mul(through)
+                        .and_then(|c| c.checked_add(*total))
+                        .ok_or(anyhow!("Too many arrangements to count"))?;
+                }
+            }
+            ways = next;
+        }
+
+        let last = self.sequences[self.sequences.len() - 1];
+        let ended = ways.get(&(0, 0)).copied().unwrap_or_default();
+        let ending = ways.get(&(-1, last)).copied().unwrap_or_default();
+        ended
+            .checked_add(ending)
+            .ok_or(anyhow!("Too many arrangements to count"))
+    }
+
+    /// Works out the `Transfer` for a run of springs, by following every
+    /// start state through it one spring at a time
+    fn transfer(&self, springs: &[Condition]) -> anyhow::Result<Transfer> {
+        let mut transfer = Transfer::new();
+        for (group, &len) in self.sequences.iter().enumerate() {
+            for run in 0..=len {
+                let mut ways: HashMap<State, u128> = HashMap::from([((group, run), 1)]);
+                for &condition in springs {
+                    let mut next = HashMap::new();
+                    for (state, count) in ways {
+                        for state in self.step(state, condition) {
+                            let total: &mut u128 = next.entry(state).or_default();
+                            *total = total
+                                .checked_add(count)
+                                .ok_or(anyhow!("Too many arrangements to count"))?;
+                        }
+                    }
+                    ways = next;
+                }
+                let ends = ways
+                    .into_iter()
+                    .map(|((done, end_run), count)| ((done - group, end_run), count))
+                    .collect();
+                transfer.insert((group, run), ends);
+            }
+        }
+        Ok(transfer)
+    }
+
+    /// The states one spring of `condition` could move `state` on to, where
+    /// groups repeat once all of the row's groups are finished
+    fn step(&self, (done, run): State, condition: Condition) -> impl Iterator<Item = State> {
+        let len = self.sequences[done % self.sequences.len()];
+        let good = match run {
+            0 => Some((done, 0)),
+            _ if run == len => Some((done + 1, 0)),
+            _ => None,
+        };
+        let bad = (run < len).then_some((done, run + 1));
+        [
+            good.filter(|_| condition != Condition::Bad),
+            bad.filter(|_| condition != Condition::Good),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Fills in `Counts` from the end of the row backwards, one group at a
+    /// time, so each entry only looks at entries already filled in. That's
+    /// one pass over the row for each group.
+    fn counts(&self) -> anyhow::Result<Counts> {
+        let len = self.conditions.len();
+        let groups = self.sequences.len();
+        let mut open = vec![0; len + 1];
+        for pos in (0..len).rev() {
+            if self.conditions[pos] != Condition::Good {
+                open[pos] = open[pos + 1] + 1;
             }
         }
 
-        let mut count = 0;
-        let space_remaining = combo.len() - filled;
-        // Could take this out of the loop, but usually not too long?
-        let space_needed: usize = sequence.iter().sum::<usize>() + sequence.len() - 1;
-        let (seq, rest) = sequence.split_first().expect("checked size");
-
-        for i in 0..=(space_remaining - space_needed) {
-            let fill_range = filled + i..filled + i + seq;
-            let check_range = filled..combo.len().min(filled + i + seq + 1);
-            combo[fill_range.clone()].fill(Condition::Bad);
-
-            // Only really need to check the current section (filled..filled+i+seq)
-            // since previous section is known to match
-            if condition_match(&self.conditions[check_range.clone()], &combo[check_range]) {
-                let key = (rest.len(), filled + i + seq);
-                let c = cache.get(&key);
-                let c = if let Some(v) = c {
-                    *v
-                } else {
-                    let c = self.combo_rec(cache, combo, filled + i + seq + 1, rest);
-                    cache.insert(key, c);
-                    c
-                };
-                count += c;
+        let mut ways = vec![vec![0; len + 1]; groups + 1];
+        ways[groups][len] = 1;
+        for pos in (0..len).rev() {
+            if self.conditions[pos] != Condition::Bad {
+                ways[groups][pos] = ways[groups][pos + 1];
+            }
+        }
+        for group in (0..groups).rev() {
+            for pos in (0..len).rev() {
+                let mut count = 0;
+                if self.conditions[pos] != Condition::Bad {
+                    count = ways[group][pos + 1];
+                }
+                if let Some(next) = self.place(&open, group, pos) {
+                    count = u128::checked_add(count, ways[group + 1][next])
+                        .ok_or(anyhow!("Too many arrangements to count"))?;
+                }
+                ways[group][pos] = count;
             }
-            combo[fill_range].fill(Condition::Good);
         }
-        count
+        Ok(Counts {
+            open: open.into(),
+            ways,
+        })
+    }
+
+    /// Whether the group at `group` can start at `pos`, given how many
+    /// springs are `open` from each position. If it can, this is where the
+    /// next group could start, after the gap following it.
+    fn place(&self, open: &[usize], group: usize, pos: usize) -> Option<usize> {
+        let end = pos + self.sequences[group];
+        if open[pos] < self.sequences[group] || self.conditions.get(end) == Some(&Condition::Bad) {
+            return None;
+        }
+        Some((end + 1).min(self.conditions.len()))
+    }
+
+    /// Walks `counts` to build the arrangement at `index`. At each spring,
+    /// arrangements starting the next group there come before those that
+    /// leave it working.
+    fn unrank(&self, counts: &Counts, mut index: u128) -> String {
+        let mut combo = vec![Condition::Good; self.conditions.len()];
+        let (mut group, mut pos) = (0, 0);
+        while group < self.sequences.len() {
+            if let Some(next) = self.place(&counts.open, group, pos) {
+                let placed = counts.ways[group + 1][next];
+                if index < placed {
+                    combo[pos..pos + self.sequences[group]].fill(Condition::Bad);
+                    group += 1;
+                    pos = next;
+                    continue;
+                }
+                index -= placed;
+            }
+            pos += 1;
+        }
+        Condition::row_string(&combo)
     }
 }
 
@@ -130,3 +302,174 @@ impl FromStr for RowCondition {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn condition_match(base: &[Condition], test: &[Condition]) -> bool {
+        assert_eq!(base.len(), test.len());
+        for (b, t) in base.iter().zip(test.iter()) {
+            if *b != Condition::Unknown && b != t {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Checks an arrangement has no unknowns, agrees with the row's known
+    /// springs and has the row's groups of damaged springs
+    fn is_valid(row: &RowCondition, arrangement: &str) -> bool {
+        let conditions: Vec<Condition> = arrangement
+            .chars()
+            .map(|c| Condition::try_from(c).unwrap())
+            .collect();
+        let groups: Vec<usize> = arrangement
+            .split('.')
+            .filter(|g| !g.is_empty())
+            .map(str::len)
+            .collect();
+        !conditions.contains(&Condition::Unknown)
+            && condition_match(&row.conditions, &conditions)
+            && groups[..] == row.sequences[..]
+    }
+
+    #[test]
+    fn arrangements() {
+        for line in crate::tests::INPUT.lines() {
+            let row: RowCondition = line.parse().unwrap();
+            let all: Vec<String> = row.arrangements().unwrap().collect();
+            assert_eq!(all.len() as u128, row.possible_combinations().unwrap());
+            assert!(all.iter().all(|a| is_valid(&row, a)));
+            let mut unique = all.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(unique.len(), all.len());
+        }
+
+        let row: RowCondition = "???.### 1,1,3".parse().unwrap();
+        assert_eq!(
+            row.arrangements().unwrap().collect::<Vec<_>>(),
+            vec!["#.#.###"]
+        );
+    }
+
+    #[test]
+    fn arrangement_by_index() {
+        let row: RowCondition = ".??..??...?##. 1,1,3".parse().unwrap();
+        assert_eq!(row.arrangement(0).unwrap().unwrap(), ".#...#....###.");
+        assert_eq!(row.arrangement(1).unwrap().unwrap(), ".#....#...###.");
+        assert_eq!(row.arrangement(3).unwrap().unwrap(), "..#...#...###.");
+        assert_eq!(row.arrangement(4).unwrap(), None);
+
+        let row: RowCondition = "#.# 2".parse().unwrap();
+        assert_eq!(row.arrangement(0).unwrap(), None);
+        assert_eq!(row.arrangements().unwrap().count(), 0);
+    }
+
+    /// A row of `len` unknown springs with `groups` groups of one damaged
+    /// spring, which has `len - groups + 1` choose `groups` arrangements
+    fn unknown_row(len: usize, groups: usize) -> RowCondition {
+        format!("{} {}", "?".repeat(len), vec!["1"; groups].join(","))
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn large_counts() {
+        let row = unknown_row(150, 20);
+        assert_eq!(
+            row.possible_combinations().unwrap(),
+            197514603495688734455200
+        );
+        assert_eq!(
+            row.arrangement(197514603495688734455199).unwrap().unwrap(),
+            format!("{}{}", ".".repeat(111), "#.".repeat(19) + "#")
+        );
+
+        let row = unknown_row(400, 100);
+        assert_eq!(
+            row.possible_combinations().unwrap_err().to_string(),
+            "Too many arrangements to count"
+        );
+        assert!(row.sample(&mut StdRng::seed_from_u64(0)).is_err());
+    }
+
+    #[test]
+    fn unfold() {
+        let row: RowCondition = ".# 1".parse().unwrap();
+        let unfolded: RowCondition = ".#?.#?.# 1,1,1".parse().unwrap();
+        assert_eq!(row.unfold(3, Condition::Unknown), unfolded);
+        let unfolded: RowCondition = ".##.# 1,1".parse().unwrap();
+        assert_eq!(row.unfold(2, Condition::Bad), unfolded);
+    }
+
+    #[test]
+    fn unfolded_matches_unfold() {
+        let rows = crate::tests::INPUT.lines().chain([
+            "???.?????##?#??????? 3,8,2",
+            ".???#??????#. 6,1",
+            "?#? 2",
+        ]);
+        for line in rows {
+            let row: RowCondition = line.parse().unwrap();
+            for separator in [Condition::Good, Condition::Bad, Condition::Unknown] {
+                for folds in 0..6 {
+                    assert_eq!(
+                        row.unfolded_combinations(folds, separator).unwrap(),
+                        row.unfold(folds, separator)
+                            .possible_combinations()
+                            .unwrap(),
+                        "{line} unfolded {folds} times with {separator:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unfolded_many_times() {
+        let row: RowCondition = "????.#...#... 4,1,1".parse().unwrap();
+        assert_eq!(
+            row.unfolded_combinations(100, Condition::Unknown).unwrap(),
+            row.unfold(100, Condition::Unknown)
+                .possible_combinations()
+                .unwrap()
+        );
+        assert!(row.unfolded_combinations(2000, Condition::Unknown).is_err());
+
+        let row: RowCondition = "???.### 1,1,3".parse().unwrap();
+        assert_eq!(
+            row.unfolded_combinations(5000, Condition::Unknown).unwrap(),
+            1
+        );
+        // Counting only drifts a group or so out of step, however many folds
+        assert_eq!(
+            row.unfolded_combinations(100_000, Condition::Unknown)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn sample_uniform() {
+        let row: RowCondition = "?###???????? 3,2,1".parse().unwrap();
+        let mut rng = StdRng::seed_from_u64(12);
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for _ in 0..2000 {
+            let arrangement = row.sample(&mut rng).unwrap().unwrap();
+            assert!(is_valid(&row, &arrangement));
+            *seen.entry(arrangement).or_default() += 1;
+        }
+        // All 10 arrangements turn up, each close to 200 times
+        assert_eq!(seen.len(), 10);
+        assert!(seen.values().all(|&n| (120..280).contains(&n)));
+
+        let row: RowCondition = "#.# 2".parse().unwrap();
+        assert_eq!(row.sample(&mut rng).unwrap(), None);
+    }
+}
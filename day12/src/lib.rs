@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use aoc_core::Solution;
+
+use condition::{Condition, RowCondition};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub mod condition;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u8 {
+        12
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> anyhow::Result<u128> {
+    input.lines().try_fold(0, |total, l| {
+        add_counts(total, RowCondition::from_str(l)?.possible_combinations()?)
+    })
+}
+
+pub fn part_two(input: &str) -> anyhow::Result<u128> {
+    #[cfg(feature = "parallel")]
+    let lines = input.par_lines();
+    #[cfg(not(feature = "parallel"))]
+    let lines = input.lines();
+    let counts: Vec<u128> = lines
+        .map(|l| RowCondition::from_str(l)?.unfolded_combinations(5, Condition::Unknown))
+        .collect::<anyhow::Result<_>>()?;
+    counts.into_iter().try_fold(0, add_counts)
+}
+
+fn add_counts(total: u128, count: u128) -> anyhow::Result<u128> {
+    total
+        .checked_add(count)
+        .ok_or(anyhow!("Too many arrangements to count"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        let mut lines = INPUT.lines();
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 1);
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 4);
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 1);
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 1);
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 4);
+        assert_eq!(part_one(lines.next().unwrap()).unwrap(), 10);
+    }
+
+    #[test]
+    fn part_one_full() {
+        assert_eq!(part_one(INPUT).unwrap(), 21);
+    }
+
+    #[test]
+    fn part_one_manual() {
+        let input = "???.?????##?#??????? 3,8,2";
+        assert_eq!(part_one(input).unwrap(), 19);
+
+        let input = ".???#??????#. 6,1";
+        assert_eq!(part_one(input).unwrap(), 4);
+
+        let input = "???##???##?#??#?#..# 1,14,1";
+        assert_eq!(part_one(input).unwrap(), 2);
+    }
+
+    #[test]
+    fn part_one_first() {
+        let input = "?##?.??.???.. 3,2,2";
+        assert_eq!(part_one(input).unwrap(), 4);
+    }
+
+    #[test]
+    fn bad_rows() {
+        assert!(part_one("???.### 1,x").is_err());
+        let huge = format!("{} {}", "?".repeat(400), vec!["1"; 100].join(","));
+        assert_eq!(
+            part_one(&huge).unwrap_err().to_string(),
+            "Too many arrangements to count"
+        );
+        assert!(part_two(&huge).is_err());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let serial: u128 = INPUT
+            .lines()
+            .map(|l| {
+                RowCondition::from_str(l)
+                    .unwrap()
+                    .unfolded_combinations(5, Condition::Unknown)
+                    .unwrap()
+            })
+            .sum();
+        assert_eq!(part_two(INPUT).unwrap(), serial);
+    }
+
+    #[test]
+    fn part_two_full() {
+        assert_eq!(part_two(INPUT).unwrap(), 525152);
+    }
+}
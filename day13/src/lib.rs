@@ -0,0 +1,74 @@
+use aoc_core::Solution;
+
+use pattern::Pattern;
+
+pub mod pattern;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn day(&self) -> u8 {
+        13
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input)?.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input)?.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> anyhow::Result<usize> {
+    summarize(input, 0)
+}
+
+pub fn part_two(input: &str) -> anyhow::Result<usize> {
+    summarize(input, 1)
+}
+
+/// Adds up the summaries of each pattern's reflection with exactly `smudges`
+/// cells that need flipping
+pub fn summarize(input: &str, smudges: usize) -> anyhow::Result<usize> {
+    input
+        .split("\n\n")
+        .map(|section| {
+            Ok(section
+                .parse::<Pattern>()?
+                .reflection(smudges)?
+                .axis
+                .summary())
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT).unwrap(), 405);
+    }
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(INPUT).unwrap(), 400);
+    }
+}
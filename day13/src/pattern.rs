@@ -1,27 +1,215 @@
-pub struct Pattern<'a> {
-    rows: Box<[&'a str]>,
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use aoc_grid::{Grid, Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ground {
+    Ash,
+    Rock,
+}
+
+impl TryFrom<char> for Ground {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Self::Ash),
+            '#' => Ok(Self::Rock),
+            c => Err(anyhow!("Unknown char {c} in pattern")),
+        }
+    }
+}
+
+impl From<Ground> for char {
+    fn from(value: Ground) -> Self {
+        match value {
+            Ground::Ash => '.',
+            Ground::Rock => '#',
+        }
+    }
+}
+
+/// A line a pattern could be reflected across
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Between two rows, with this many rows above it
+    Horizontal(usize),
+    /// Between two columns, with this many columns to its left
+    Vertical(usize),
 }
 
-impl<'a> Pattern<'a> {
-    pub fn new(s: &'a str) -> Self {
-        // Should check all rows are equal length
-        Pattern {
-            rows: s.lines().collect(),
+impl Axis {
+    /// The number the puzzle sums up for a reflection across this axis
+    pub fn summary(&self) -> usize {
+        match self {
+            Self::Horizontal(above) => 100 * above,
+            Self::Vertical(left) => *left,
         }
     }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    /// One cell out of each pair the axis maps onto each other that differ,
+    /// taken from above or left of the axis. Flipping these makes the
+    /// reflection perfect, as would flipping the cells they mirror instead.
+    pub flips: Vec<Position>,
+}
+
+impl Reflection {
+    /// How many pairs of cells differ across the axis
+    pub fn mismatches(&self) -> usize {
+        self.flips.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    grid: Grid<Ground>,
+}
 
-    pub fn get_row(&self, i: usize) -> Option<String> {
-        self.rows.get(i).map(|&s| s.to_owned())
+impl FromStr for Pattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self { grid: s.parse()? })
     }
-    pub fn get_column(&self, i: usize) -> Option<String> {
-        self.rows.iter().map(|r| r.chars().nth(i)).collect()
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
     }
+}
 
+impl Pattern {
     pub fn rows(&self) -> usize {
-        self.rows.len()
+        self.grid.height()
     }
 
     pub fn columns(&self) -> usize {
-        self.rows.first().map_or(0, |r| r.len())
+        self.grid.width()
+    }
+
+    /// Swaps the ash or rock at `(x, y)` for the other
+    pub fn flip(&mut self, (x, y): Position) {
+        self.grid[(x, y)] = match self.grid[(x, y)] {
+            Ground::Ash => Ground::Rock,
+            Ground::Rock => Ground::Ash,
+        };
+    }
+
+    /// Every axis the pattern could be reflected across, however many
+    /// mismatches it has. Horizontal axes come first, from the top down,
+    /// then vertical axes from the left.
+    pub fn reflections(&self) -> impl Iterator<Item = Reflection> + '_ {
+        let horizontal = (1..self.rows()).map(Axis::Horizontal);
+        let vertical = (1..self.columns()).map(Axis::Vertical);
+        horizontal.chain(vertical).map(|axis| Reflection {
+            axis,
+            flips: self.flips(axis),
+        })
+    }
+
+    /// The first reflection that is exactly `smudges` flipped cells away from
+    /// being perfect
+    pub fn reflection(&self, smudges: usize) -> anyhow::Result<Reflection> {
+        self.reflections()
+            .find(|r| r.mismatches() == smudges)
+            .ok_or(anyhow!(
+                "No reflection with {smudges} smudges in pattern\n{self}"
+            ))
+    }
+
+    fn flips(&self, axis: Axis) -> Vec<Position> {
+        // Lines are rows or columns, and `at` finds the cell `i` along one
+        let (size, lines, length, at): (_, _, _, fn(usize, usize) -> Position) = match axis {
+            Axis::Horizontal(above) => (above, self.rows(), self.columns(), |line, i| (i, line)),
+            Axis::Vertical(left) => (left, self.columns(), self.rows(), |line, i| (line, i)),
+        };
+        // Pairs of near and far lines either side of the axis, until one
+        // runs out of the pattern
+        (0..size.min(lines - size))
+            .flat_map(|offset| {
+                let (near, far) = (size - 1 - offset, size + offset);
+                (0..length)
+                    .map(move |i| (at(near, i), at(far, i)))
+                    .filter(|&(a, b)| self.grid[a] != self.grid[b])
+                    .map(|(a, _)| a)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns() -> Vec<Pattern> {
+        crate::tests::INPUT
+            .split("\n\n")
+            .map(|p| p.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn every_axis() {
+        let patterns = patterns();
+        let reflections: Vec<Reflection> = patterns[0].reflections().collect();
+        assert_eq!(reflections.len(), 6 + 8);
+        let perfect: Vec<Axis> = reflections
+            .iter()
+            .filter(|r| r.mismatches() == 0)
+            .map(|r| r.axis)
+            .collect();
+        assert_eq!(perfect, vec![Axis::Vertical(5)]);
+
+        // Row 0 and row 1 differ in 5 of their 9 columns
+        assert_eq!(reflections[0].axis, Axis::Horizontal(1));
+        assert_eq!(
+            reflections[0].flips,
+            vec![(0, 0), (3, 0), (4, 0), (5, 0), (6, 0)]
+        );
+    }
+
+    #[test]
+    fn smudges() {
+        let patterns = patterns();
+        let reflection = patterns[0].reflection(1).unwrap();
+        assert_eq!(reflection.axis, Axis::Horizontal(3));
+        assert_eq!(reflection.flips, vec![(0, 0)]);
+
+        let reflection = patterns[1].reflection(1).unwrap();
+        assert_eq!(reflection.axis, Axis::Horizontal(1));
+        assert_eq!(reflection.flips, vec![(4, 0)]);
+    }
+
+    #[test]
+    fn flips_make_reflection_perfect() {
+        for pattern in patterns() {
+            for reflection in pattern.reflections() {
+                let mut fixed = pattern.clone();
+                for &cell in &reflection.flips {
+                    fixed.flip(cell);
+                }
+                let axes: Vec<Axis> = fixed
+                    .reflections()
+                    .filter(|r| r.mismatches() == 0)
+                    .map(|r| r.axis)
+                    .collect();
+                assert!(axes.contains(&reflection.axis));
+            }
+        }
+    }
+
+    #[test]
+    fn no_reflection() {
+        let pattern: Pattern = "#.\n..".parse().unwrap();
+        assert!(pattern.reflection(0).is_err());
+        assert_eq!(pattern.reflection(1).unwrap().axis, Axis::Horizontal(1));
+        assert!(pattern.reflection(2).is_err());
+        assert!("#.\n.".parse::<Pattern>().is_err());
     }
 }
@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use aoc_core::Solution;
+
+use platform::{Direction, Platform};
+
+pub mod platform;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn day(&self) -> u8 {
+        14
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> anyhow::Result<usize> {
+    let mut platform = Platform::from_str(input)?;
+    platform.tilt(Direction::North);
+
+    Ok(platform.load())
+}
+
+pub fn part_two(input: &str) -> anyhow::Result<usize> {
+    let mut platform = Platform::from_str(input)?;
+    platform.spin_n(1_000_000_000);
+
+    Ok(platform.load())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#...."
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert!(part_one(INPUT).is_ok_and(|l| l == 136));
+    }
+
+    #[test]
+    fn spin_cycle() {
+        let mut platform = Platform::from_str(INPUT).unwrap();
+        platform.spin();
+        platform.spin();
+        platform.spin();
+
+        let expected = Platform::from_str(
+            ".....#....
+....#...O#
+.....##...
+..O#......
+.....OOO#.
+.O#...O#.#
+....O#...O
+.......OOO
+#...O###.O
+#.OOO#...O",
+        )
+        .unwrap();
+        assert_eq!(platform, expected)
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert!(part_two(INPUT).is_ok_and(|l| l == 64));
+    }
+}
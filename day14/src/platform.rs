@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
+use aoc_grid::Grid;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Rock {
@@ -28,89 +29,236 @@ pub enum Direction {
     West,
 }
 
-impl Direction {
-    fn y_range(self, min: usize, max: usize) -> Box<dyn Iterator<Item = usize>> {
-        let range = min..max;
-        match self {
-            Self::South => Box::new(range.rev()),
-            _ => Box::new(range),
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' => Ok(Self::North),
+            'S' => Ok(Self::South),
+            'E' => Ok(Self::East),
+            'W' => Ok(Self::West),
+            c => Err(anyhow!("Unknown direction {c}")),
         }
     }
-    fn x_range(self, min: usize, max: usize) -> Box<dyn Iterator<Item = usize>> {
-        let range = min..max;
-        match self {
-            Self::East => Box::new(range.rev()),
-            _ => Box::new(range),
-        }
+}
+
+/// One spin cycle: north, then west, then south, then east
+const SPIN: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
+
+/// A sequence of tilts, written like `NWSE`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TiltProgram {
+    tilts: Box<[Direction]>,
+}
+
+impl TiltProgram {
+    pub fn spin() -> Self {
+        Self { tilts: SPIN.into() }
     }
 
-    fn add_offset(self, x: usize, y: usize) -> Option<(usize, usize)> {
-        match self {
-            Self::North => Some(x).zip(y.checked_sub(1)),
-            Self::South => Some(x).zip(y.checked_add(1)),
-            Self::East => x.checked_add(1).zip(Some(y)),
-            Self::West => x.checked_sub(1).zip(Some(y)),
-        }
+    pub fn tilts(&self) -> &[Direction] {
+        &self.tilts
+    }
+}
+
+impl FromStr for TiltProgram {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            tilts: s
+                .trim()
+                .chars()
+                .map(Direction::try_from)
+                .collect::<anyhow::Result<_>>()?,
+        })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Platform {
-    rocks: Box<[Option<Rock>]>,
-    width: usize,
+    rocks: Grid<Option<Rock>>,
 }
 
 impl Platform {
     pub fn width(&self) -> usize {
-        self.width
+        self.rocks.width()
     }
     pub fn height(&self) -> usize {
-        self.rocks.len() / self.width
+        self.rocks.height()
     }
     pub fn load(&self) -> usize {
-        let mut load = 0;
-        for col in 0..self.height() {
-            for row in 0..self.width() {
-                if self.get(row, col) == Some(Rock::Round) {
-                    load += self.height() - col;
-                }
-            }
-        }
-        load
+        self.load_towards(Direction::North)
     }
+    /// The load on the support beams along the `dir` edge, where each round
+    /// rock counts for its distance from the opposite edge
+    pub fn load_towards(&self, dir: Direction) -> usize {
+        let (width, height) = (self.width(), self.height());
+        self.rocks
+            .iter()
+            .filter(|(_, rock)| **rock == Some(Rock::Round))
+            .map(|((x, y), _)| match dir {
+                Direction::North => height - y,
+                Direction::South => y + 1,
+                Direction::West => width - x,
+                Direction::East => x + 1,
+            })
+            .sum()
+    }
+    /// Slides every round rock as far as it goes in `dir`. Each row or
+    /// column is swept once from the edge the rocks are moving towards,
+    /// keeping track of the next free spot for a round rock to land in.
     pub fn tilt(&mut self, dir: Direction) {
-        for y in dir.y_range(0, self.height()) {
-            for x in dir.x_range(0, self.width()) {
-                if self.get(x, y) == Some(Rock::Round) {
-                    let (mut nx, mut ny) = (x, y);
-                    while let Some(pos) = dir.add_offset(nx, ny).filter(|(x, y)| {
-                        *x < self.width && *y < self.height() && self.get(*x, *y).is_none()
-                    }) {
-                        (nx, ny) = pos;
+        let (lines, len) = match dir {
+            Direction::North | Direction::South => (self.width(), self.height()),
+            Direction::East | Direction::West => (self.height(), self.width()),
+        };
+        // Position of the `i`th cell along a line, counting from the edge
+        let pos = |line: usize, i: usize| match dir {
+            Direction::North => (line, i),
+            Direction::South => (line, len - 1 - i),
+            Direction::West => (i, line),
+            Direction::East => (len - 1 - i, line),
+        };
+        for line in 0..lines {
+            let mut free = 0;
+            for i in 0..len {
+                let p = pos(line, i);
+                match self.rocks[p] {
+                    Some(Rock::Cube) => free = i + 1,
+                    Some(Rock::Round) => {
+                        self.rocks[p] = None;
+                        self.rocks[pos(line, free)] = Some(Rock::Round);
+                        free += 1;
                     }
-                    self.set(x, y, None);
-                    self.set(nx, ny, Some(Rock::Round));
+                    None => (),
                 }
             }
         }
     }
 
     pub fn get(&self, x: usize, y: usize) -> Option<Rock> {
-        self.rocks.get(y * self.width + x).copied().flatten()
+        self.rocks.get(x, y).copied().flatten()
     }
     pub fn set(&mut self, x: usize, y: usize, rock: Option<Rock>) -> bool {
-        match self.rocks.get_mut(y * self.width + x) {
-            Some(r) => *r = rock,
-            None => return false,
-        }
-        true
+        self.rocks.set(x, y, rock)
     }
 
     pub fn spin(&mut self) {
-        self.tilt(Direction::North);
-        self.tilt(Direction::West);
-        self.tilt(Direction::South);
-        self.tilt(Direction::East);
+        for dir in SPIN {
+            self.tilt(dir);
+        }
+    }
+
+    /// Tilts in each direction of the program in turn
+    pub fn run(&mut self, program: &TiltProgram) {
+        for &dir in program.tilts() {
+            self.tilt(dir);
+        }
+    }
+
+    /// Spins `n` times. See `run_n`.
+    pub fn spin_n(&mut self, n: usize) -> SpinCycle {
+        self.run_n(&TiltProgram::spin(), n)
+    }
+
+    /// Runs the program `n` times. Running it over and over always ends up
+    /// going round in a cycle, so once the cycle is found any whole laps of
+    /// it are skipped.
+    ///
+    /// The cycle is found with Brent's algorithm, which only keeps two
+    /// platforms around at a time rather than every one it has seen.
+    pub fn run_n(&mut self, program: &TiltProgram, n: usize) -> SpinCycle {
+        let start = self.clone();
+        let spun = |mut platform: Platform| {
+            platform.run(program);
+            platform
+        };
+
+        // Find the cycle length by moving the tortoise up to the hare every
+        // power of two spins, until the hare laps back round to it
+        let (mut power, mut length) = (1, 1);
+        let mut tortoise = start.clone();
+        let mut hare = spun(start.clone());
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = spun(hare);
+            length += 1;
+        }
+
+        // Then with the hare a cycle ahead, they first meet where the cycle
+        // starts. The loads are kept along the way.
+        let mut loads = vec![start.load()];
+        let mut tortoise = start.clone();
+        let mut hare = start;
+        for _ in 0..length {
+            hare = spun(hare);
+            loads.push(hare.load());
+        }
+        let mut cycle_start = 0;
+        while tortoise != hare {
+            tortoise = spun(tortoise);
+            hare = spun(hare);
+            loads.push(hare.load());
+            cycle_start += 1;
+        }
+        loads.truncate(cycle_start + length);
+
+        let cycle = SpinCycle {
+            start: cycle_start,
+            length,
+            loads,
+        };
+        let step = cycle.step(n);
+        if step >= cycle_start {
+            *self = tortoise;
+            for _ in cycle_start..step {
+                self.run(program);
+            }
+        } else {
+            for _ in 0..step {
+                self.run(program);
+            }
+        }
+        cycle
+    }
+}
+
+/// Where running a tilt program over and over starts repeating itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpinCycle {
+    /// Runs before the first repeated platform
+    pub start: usize,
+    /// Runs between repeats
+    pub length: usize,
+    /// The load before any runs, then after each run up to the end of the
+    /// first lap of the cycle
+    loads: Vec<usize>,
+}
+
+impl SpinCycle {
+    /// The earliest step that leaves the platform the same as `n` runs
+    fn step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The load on the north support beams after `n` runs
+    pub fn load(&self, n: usize) -> usize {
+        self.loads[self.step(n)]
     }
 }
 
@@ -118,21 +266,153 @@ impl FromStr for Platform {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width = None;
-        let mut rocks = Vec::new();
-        for l in s.lines() {
-            if let Some(w) = width {
-                if l.len() != w {
-                    return Err(anyhow!("Not all lines are the same length"));
+        Ok(Self {
+            rocks: Grid::parse_with(s, |c| Ok(Rock::try_from(c).ok()))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    impl Direction {
+        fn y_range(self, min: usize, max: usize) -> Box<dyn Iterator<Item = usize>> {
+            let range = min..max;
+            match self {
+                Self::South => Box::new(range.rev()),
+                _ => Box::new(range),
+            }
+        }
+        fn x_range(self, min: usize, max: usize) -> Box<dyn Iterator<Item = usize>> {
+            let range = min..max;
+            match self {
+                Self::East => Box::new(range.rev()),
+                _ => Box::new(range),
+            }
+        }
+
+        fn add_offset(self, x: usize, y: usize) -> Option<(usize, usize)> {
+            match self {
+                Self::North => Some(x).zip(y.checked_sub(1)),
+                Self::South => Some(x).zip(y.checked_add(1)),
+                Self::East => x.checked_add(1).zip(Some(y)),
+                Self::West => x.checked_sub(1).zip(Some(y)),
+            }
+        }
+    }
+
+    impl Platform {
+        /// The original tilt, moving each rock a step at a time
+        fn tilt_by_steps(&mut self, dir: Direction) {
+            for y in dir.y_range(0, self.height()) {
+                for x in dir.x_range(0, self.width()) {
+                    if self.get(x, y) == Some(Rock::Round) {
+                        let (mut nx, mut ny) = (x, y);
+                        while let Some(pos) = dir.add_offset(nx, ny).filter(|(x, y)| {
+                            *x < self.width() && *y < self.height() && self.get(*x, *y).is_none()
+                        }) {
+                            (nx, ny) = pos;
+                        }
+                        self.set(x, y, None);
+                        self.set(nx, ny, Some(Rock::Round));
+                    }
                 }
-            } else {
-                width = Some(l.len());
             }
-            rocks.extend(l.chars().map(|c| Rock::try_from(c).ok()))
         }
-        Ok(Self {
-            rocks: rocks.into(),
-            width: width.unwrap_or(0),
+    }
+
+    fn platform() -> impl Strategy<Value = Platform> {
+        (1..12usize, 1..12usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(
+                prop_oneof![Just(None), Just(Some(Rock::Round)), Just(Some(Rock::Cube))],
+                width * height,
+            )
+            .prop_map(move |cells| Platform {
+                rocks: Grid::new(cells, width).unwrap(),
+            })
         })
     }
+
+    proptest! {
+        #[test]
+        fn tilt_matches_steps(platform in platform()) {
+            for dir in [Direction::North, Direction::South, Direction::East, Direction::West] {
+                let mut fast = platform.clone();
+                fast.tilt(dir);
+                let mut slow = platform.clone();
+                slow.tilt_by_steps(dir);
+                prop_assert_eq!(fast, slow);
+            }
+        }
+    }
+
+    #[test]
+    fn spin_n() {
+        let platform: Platform = crate::tests::INPUT.parse().unwrap();
+        let mut spun = platform.clone();
+        let cycle = spun.spin_n(1_000_000_000);
+        assert_eq!((cycle.start, cycle.length), (3, 7));
+        assert_eq!(spun.load(), 64);
+        assert_eq!(cycle.load(1_000_000_000), 64);
+
+        let mut expected = platform.clone();
+        for n in 0..30 {
+            assert_eq!(cycle.load(n), expected.load(), "after {n} spins");
+            let mut spun = platform.clone();
+            spun.spin_n(n);
+            assert_eq!(spun, expected, "after {n} spins");
+            expected.spin();
+        }
+    }
+
+    #[test]
+    fn spin_n_still() {
+        // Nothing can move, so it repeats straight away
+        let mut platform: Platform = "#.\n.#".parse().unwrap();
+        let cycle = platform.spin_n(5);
+        assert_eq!((cycle.start, cycle.length), (0, 1));
+        assert_eq!(cycle.load(5), 0);
+    }
+
+    #[test]
+    fn tilt_programs() {
+        let platform: Platform = crate::tests::INPUT.parse().unwrap();
+        let program: TiltProgram = "NWSE".parse().unwrap();
+        assert_eq!(program, TiltProgram::spin());
+        let mut run = platform.clone();
+        run.run(&program);
+        let mut spun = platform.clone();
+        spun.spin();
+        assert_eq!(run, spun);
+
+        let program: TiltProgram = "NNE".parse().unwrap();
+        assert_eq!(
+            program.tilts(),
+            [Direction::North, Direction::North, Direction::East]
+        );
+        let mut expected = platform.clone();
+        for _ in 0..5 {
+            expected.run(&program);
+        }
+        let mut run = platform.clone();
+        run.run_n(&program, 5);
+        assert_eq!(run, expected);
+
+        assert!("NWX".parse::<TiltProgram>().is_err());
+    }
+
+    #[test]
+    fn load_towards() {
+        // ...
+        // O..
+        // .#O
+        let platform: Platform = "...\nO..\n.#O".parse().unwrap();
+        assert_eq!(platform.load_towards(Direction::North), 3);
+        assert_eq!(platform.load(), 3);
+        assert_eq!(platform.load_towards(Direction::South), 5);
+        assert_eq!(platform.load_towards(Direction::West), 4);
+        assert_eq!(platform.load_towards(Direction::East), 4);
+    }
 }
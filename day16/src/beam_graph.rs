@@ -0,0 +1,229 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::heat_splitter::{Beam, Direction, HeatSplitter};
+
+/// Every beam state in a contraption, (position, direction), and the states
+/// it leads to. States that lead round in a loop to each other are
+/// strongly connected, so they all energize the same tiles. Condensing each
+/// loop down to one node leaves a graph with no cycles, where the tiles
+/// energized from a node are its own plus those of the nodes after it.
+pub struct BeamGraph {
+    width: usize,
+    /// The strongly connected component each state belongs to
+    component: Vec<usize>,
+    /// The number of tiles energized from each component
+    counts: Vec<usize>,
+}
+
+impl BeamGraph {
+    pub fn new(contraption: &HeatSplitter) -> Self {
+        let (width, height) = (contraption.width(), contraption.height());
+        let successors = all_successors(contraption);
+
+        let (component, components) = tarjan(&successors);
+        let mut members = vec![Vec::new(); components];
+        for (state, &c) in component.iter().enumerate() {
+            members[c].push(state);
+        }
+        let next: Vec<Vec<usize>> = members
+            .iter()
+            .enumerate()
+            .map(|(c, states)| {
+                let mut next: Vec<usize> = states
+                    .iter()
+                    .flat_map(|&s| &successors[s])
+                    .map(|&s| component[s])
+                    .filter(|&n| n != c)
+                    .collect();
+                next.sort_unstable();
+                next.dedup();
+                next
+            })
+            .collect();
+
+        // Tarjan's algorithm finds the components after everything they lead
+        // to, so each component's tiles can be built from ones already done.
+        // A component's tiles are dropped once everything leading to it has
+        // used them.
+        let mut waiting = vec![0; components];
+        for &n in next.iter().flatten() {
+            waiting[n] += 1;
+        }
+        let words = (width * height).div_ceil(64);
+        let mut tiles: Vec<Option<Vec<u64>>> = vec![None; components];
+        let mut counts = vec![0; components];
+        for c in 0..components {
+            let mut energized = vec![0u64; words];
+            for &state in &members[c] {
+                let tile = state / 4;
+                energized[tile / 64] |= 1 << (tile % 64);
+            }
+            for &n in &next[c] {
+                let after = tiles[n].as_ref().expect("Components are done in order");
+                for (word, other) in energized.iter_mut().zip(after) {
+                    *word |= other;
+                }
+                waiting[n] -= 1;
+                if waiting[n] == 0 {
+                    tiles[n] = None;
+                }
+            }
+            counts[c] = energized.iter().map(|w| w.count_ones() as usize).sum();
+            if waiting[c] > 0 {
+                tiles[c] = Some(energized);
+            }
+        }
+
+        Self {
+            width,
+            component,
+            counts,
+        }
+    }
+
+    /// Same as `HeatSplitter::energize`, for a beam inside the contraption
+    pub fn energized_count(&self, beam: Beam) -> usize {
+        self.counts[self.component[node(self.width, beam)]]
+    }
+}
+
+fn node(width: usize, beam: Beam) -> usize {
+    (beam.y * width + beam.x) * 4 + beam.direction as usize
+}
+
+/// The states one step on from each state, in order
+fn all_successors(contraption: &HeatSplitter) -> Vec<Vec<usize>> {
+    let states = contraption.width() * contraption.height() * 4;
+    #[cfg(feature = "parallel")]
+    let states = (0..states).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let states = 0..states;
+    states.map(|state| successors(contraption, state)).collect()
+}
+
+/// The states one step on from `state`
+fn successors(contraption: &HeatSplitter, state: usize) -> Vec<usize> {
+    let width = contraption.width();
+    let mut beams = vec![beam(width, state)];
+    contraption.step(&mut beams);
+    beams.iter().map(|&b| node(width, b)).collect()
+}
+
+fn beam(width: usize, node: usize) -> Beam {
+    let direction = match node % 4 {
+        0 => Direction::Up,
+        1 => Direction::Down,
+        2 => Direction::Left,
+        _ => Direction::Right,
+    };
+    let tile = node / 4;
+    Beam {
+        direction,
+        x: tile % width,
+        y: tile / width,
+    }
+}
+
+/// Tarjan's strongly connected components algorithm, without recursion so a
+/// long chain of states can't overflow the stack. Returns the component of
+/// each node and the number of components, which are numbered so that edges
+/// between components always go to a lower number.
+fn tarjan(successors: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let n = successors.len();
+    let unvisited = usize::MAX;
+    let mut index = vec![unvisited; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![0; n];
+    let mut components = 0;
+    let mut next_index = 0;
+    // (node, how many of its successors have been looked at)
+    let mut calls: Vec<(usize, usize)> = Vec::new();
+
+    for root in 0..n {
+        if index[root] != unvisited {
+            continue;
+        }
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        calls.push((root, 0));
+
+        while let Some((v, i)) = calls.last_mut() {
+            let v = *v;
+            if let Some(&w) = successors[v].get(*i) {
+                *i += 1;
+                if index[w] == unvisited {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component[w] = components;
+                    if w == v {
+                        break;
+                    }
+                }
+                components += 1;
+            }
+        }
+    }
+    (component, components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_energize() {
+        let contraption: HeatSplitter = crate::tests::INPUT.parse().unwrap();
+        let graph = BeamGraph::new(&contraption);
+        for beam in contraption.edge_beams() {
+            assert_eq!(
+                graph.energized_count(beam),
+                contraption.energize(beam),
+                "{beam:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let contraption: HeatSplitter = crate::tests::INPUT.parse().unwrap();
+        let states = contraption.width() * contraption.height() * 4;
+        let serial: Vec<Vec<usize>> = (0..states)
+            .map(|state| successors(&contraption, state))
+            .collect();
+        assert_eq!(all_successors(&contraption), serial);
+    }
+
+    #[test]
+    fn components() {
+        // 0 -> 1 -> 2 -> 1, 2 -> 3
+        let (component, count) = tarjan(&[vec![1], vec![2], vec![1, 3], vec![]]);
+        assert_eq!(count, 3);
+        assert_eq!(component[1], component[2]);
+        assert!(component[3] < component[1]);
+        assert!(component[1] < component[0]);
+    }
+}
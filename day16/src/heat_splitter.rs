@@ -5,6 +5,7 @@ use std::{
 };
 
 use anyhow::anyhow;
+use aoc_grid::Grid;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
 pub enum Direction {
@@ -150,14 +151,32 @@ impl TryFrom<char> for Cell {
     }
 }
 
+impl From<Cell> for char {
+    fn from(value: Cell) -> Self {
+        match value {
+            Cell::Empty => '.',
+            Cell::Mirror(Mirror::Right) => '/',
+            Cell::Mirror(Mirror::Left) => '\\',
+            Cell::Splitter(Splitter::Vertical) => '|',
+            Cell::Splitter(Splitter::Horizontal) => '-',
+        }
+    }
+}
+
+/// Every tile a beam passed through, with the directions it was going in
+pub type Energized = BTreeMap<(usize, usize), BTreeSet<Direction>>;
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct HeatSplitter {
-    cells: Box<[Cell]>,
-    width: usize,
+    cells: Grid<Cell>,
 }
 
 impl HeatSplitter {
     pub fn energize(&self, input: Beam) -> usize {
+        self.energized(input).len()
+    }
+
+    pub fn energized(&self, input: Beam) -> Energized {
         let mut beams = Vec::new();
         beams.push(input);
 
@@ -177,8 +196,38 @@ impl HeatSplitter {
                     .insert(b.direction)
             });
         }
-        historic_beams.len()
+        historic_beams
+    }
+
+    /// The contraption with the beams drawn over the empty tiles, as an arrow
+    /// where one beam passed through or the number of beams where there were
+    /// more
+    pub fn render_beams(&self, energized: &Energized) -> String {
+        let mut grid = self.cells.map(|&c| char::from(c));
+        for (&(x, y), directions) in energized {
+            if self.get(x, y) != Some(Cell::Empty) {
+                continue;
+            }
+            grid[(x, y)] = match directions.iter().collect::<Vec<_>>()[..] {
+                [Direction::Up] => '^',
+                [Direction::Down] => 'v',
+                [Direction::Left] => '<',
+                [Direction::Right] => '>',
+                _ => char::from_digit(directions.len() as u32, 10).unwrap_or('*'),
+            };
+        }
+        grid.render(|&c| c)
     }
+
+    /// Energized tiles as `#` and the rest as `.`
+    pub fn render_energized(&self, energized: &Energized) -> String {
+        let mut grid = self.cells.map(|_| '.');
+        for &(x, y) in energized.keys() {
+            grid.set(x, y, '#');
+        }
+        grid.render(|&c| c)
+    }
+
     pub fn step(&self, beams: &mut Vec<Beam>) {
         let prev = mem::replace(beams, Vec::with_capacity(beams.len()));
 
@@ -187,19 +236,32 @@ impl HeatSplitter {
                 .unwrap_or(Cell::Empty)
                 .activate(b)
                 .into_iter()
-                .filter(|b| b.x < self.width && b.y < self.height())
+                .filter(|b| self.cells.contains((b.x, b.y)))
         }));
     }
 
     fn get(&self, x: usize, y: usize) -> Option<Cell> {
-        self.cells.get(y * self.width + x).copied()
+        self.cells.get(x, y).copied()
+    }
+
+    /// Every beam that can shine in from the edge of the contraption: down
+    /// from the top, up from the bottom, then right from the left side and
+    /// left from the right side
+    pub fn edge_beams(&self) -> impl Iterator<Item = Beam> {
+        let (width, height) = (self.width(), self.height());
+        let beam = |direction, x, y| Beam { direction, x, y };
+        (0..width)
+            .map(move |x| beam(Direction::Down, x, 0))
+            .chain((0..width).map(move |x| beam(Direction::Up, x, height - 1)))
+            .chain((0..height).map(move |y| beam(Direction::Right, 0, y)))
+            .chain((0..height).map(move |y| beam(Direction::Left, width - 1, y)))
     }
 
     pub fn height(&self) -> usize {
-        self.cells.len() / self.width
+        self.cells.height()
     }
     pub fn width(&self) -> usize {
-        self.width
+        self.cells.width()
     }
 }
 
@@ -207,19 +269,51 @@ impl FromStr for HeatSplitter {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let width = s.lines().next().map_or(0, |l| l.len());
-        let mut cells = Vec::with_capacity(s.len());
-        for l in s.lines() {
-            if l.len() != width {
-                return Err(anyhow!("Lines in input with differing lengths."));
-            }
-            for c in l.chars() {
-                cells.push(Cell::try_from(c)?);
-            }
-        }
-        Ok(Self {
-            cells: cells.into(),
-            width,
-        })
+        Ok(Self { cells: s.parse()? })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beam_overlay() {
+        let contraption: HeatSplitter = crate::tests::INPUT.parse().unwrap();
+        let energized = contraption.energized(Beam {
+            direction: Direction::Right,
+            x: 0,
+            y: 0,
+        });
+        assert_eq!(
+            energized[&(5, 6)],
+            BTreeSet::from([Direction::Down, Direction::Right])
+        );
+        assert_eq!(
+            contraption.render_beams(&energized),
+            ">|<<<\\....
+|v-.\\^....
+.v...|->>>
+.v...v^.|.
+.v...v^...
+.v...v^..\\
+.v../2\\\\..
+<->-/vv|..
+.|<<<2-|.\\
+.v//.|.v.."
+        );
+        assert_eq!(
+            contraption.render_energized(&energized),
+            "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#.."
+        );
     }
 }
@@ -0,0 +1,84 @@
+use aoc_core::Solution;
+
+use beam_graph::BeamGraph;
+use heat_splitter::{Beam, Direction, HeatSplitter};
+
+pub mod beam_graph;
+pub mod heat_splitter;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u8 {
+        16
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> usize {
+    let contraption: HeatSplitter = input.parse().unwrap();
+    contraption.energize(Beam {
+        direction: Direction::Right,
+        x: 0,
+        y: 0,
+    })
+}
+
+pub fn part_two(input: &str) -> usize {
+    best_entry(input).1
+}
+
+/// The beam from the edge that energizes the most tiles, and how many it does
+pub fn best_entry(input: &str) -> (Beam, usize) {
+    let contraption: HeatSplitter = input.parse().unwrap();
+    let graph = BeamGraph::new(&contraption);
+    contraption
+        .edge_beams()
+        .map(|beam| (beam, graph.energized_count(beam)))
+        .max_by_key(|(_, heat)| *heat)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        ".|...\\....
+|.-.\\.....
+.....|-...
+........|.
+..........
+.........\\
+..../.\\\\..
+.-.-/..|..
+.|....-|.\\
+..//.|...."
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT), 46);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(INPUT), 51);
+        assert_eq!(
+            best_entry(INPUT),
+            (
+                Beam {
+                    direction: Direction::Down,
+                    x: 3,
+                    y: 0
+                },
+                51
+            )
+        );
+    }
+}
@@ -1,83 +1,44 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use anyhow::{anyhow, Context, Result};
+use aoc_grid::Grid;
+use aoc_path::Path;
 
 use crate::point::Point;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum Dir {
+pub enum Dir {
     Up,
     Down,
     Left,
     Right,
 }
 
-pub fn parse_map(input: &str) -> (Box<[usize]>, usize, usize) {
-    let width = input.lines().next().expect("at least one line").len();
-    let height = input.lines().count();
-    let map: Box<[usize]> = input
-        .lines()
-        .flat_map(|l| {
-            l.chars()
-                .map(|c| usize::from_str(&format!("{c}")).expect("Only numeric character"))
-        })
-        .collect();
-    assert!(map.len() / height == width);
-    assert!(map.len() % height == 0);
-    (map, height, width)
+/// Where the crucible is, the direction it last moved and how many more
+/// blocks than one it has moved in that direction
+pub type Crucible = (Point, Dir, usize);
+
+pub fn parse_map(input: &str) -> Result<Grid<usize>> {
+    Grid::parse_with(input, |c| {
+        c.to_digit(10)
+            .map(|d| d as usize)
+            .ok_or_else(|| anyhow!("Invalid heat loss {c:?}"))
+    })
 }
 
-pub fn dijkstra(input: &str, min: usize, max: usize) -> usize {
-    let (map, height, width) = parse_map(input);
+pub fn dijkstra(input: &str, min: usize, max: usize) -> Result<Path<Crucible>> {
+    let map = parse_map(input)?;
+    let (width, height) = (map.width(), map.height());
     let start = Point { x: 0, y: 0 };
     let finish = Point {
         x: width - 1,
         y: height - 1,
     };
-    let mut costs = HashMap::new();
-    let mut unvis = HashSet::new();
-    for x in 0..width {
-        for y in 0..height {
-            for dir in 0..4 {
-                for run in 0..max {
-                    let p = Point { x, y };
-                    let dir = match dir {
-                        0 => Dir::Up,
-                        1 => Dir::Down,
-                        2 => Dir::Left,
-                        3 => Dir::Right,
-                        _ => unreachable!(),
-                    };
-                    unvis.insert((p, dir, run));
-                }
-            }
-        }
-    }
-    costs.insert((start, Dir::Up, 0), 0);
-    costs.insert((start, Dir::Down, 0), 0);
-    costs.insert((start, Dir::Left, 0), 0);
-    costs.insert((start, Dir::Right, 0), 0);
-    let mut count = 0;
-
-    loop {
-        let (key, _) = costs
-            .iter()
-            .min_by_key(|&(_, v)| *v)
-            .expect("Will always find the key");
-        unvis.remove(key);
-        let ((point, dir, run), cost) = costs.remove_entry(&key.clone()).expect("Valid key");
-        count += 1;
-        if count % 1000 == 0 {
-            print!("{count}/{}\r", height * width * max * 4);
-        }
+    let starts = [Dir::Up, Dir::Down, Dir::Left, Dir::Right].map(|d| (start, d, 0));
 
+    let successors = |&(point, dir, run): &Crucible| {
+        let mut next = Vec::with_capacity(3);
         if point == finish {
-            if run >= min {
-                return cost;
-            } else {
-                continue;
-            }
+            // Arriving too early doesn't count, and there's nowhere better to go
+            return next;
         }
 
         let mut add_dir = |d: Dir| {
@@ -87,39 +48,34 @@ pub fn dijkstra(input: &str, min: usize, max: usize) -> usize {
                 Dir::Left => point - (1, 0),
                 Dir::Right => point + (1, 0),
             };
-            if let Some(p) = p {
-                if p.x < width && p.y < height {
-                    let i = p.y * width + p.x;
-                    let r = if dir == d { run + 1 } else { 0 };
-                    let key = (p, d, r);
-                    if unvis.contains(&key) {
-                        let c = costs.entry(key).or_insert(usize::MAX);
-                        *c = (*c).min(cost + map[i]);
-                    }
-                }
+            if let Some(p) = p.filter(|p| p.x < width && p.y < height) {
+                let r = if dir == d { run + 1 } else { 0 };
+                next.push(((p, d, r), map[(p.x, p.y)]));
             }
         };
 
         // update cost of left, right and forward if run less than 2
         let before_range = run < min.saturating_sub(1); // only forward
         let in_range = run >= min.saturating_sub(1) && run < max - 1;
-        // println!("{point} {dir:?} {run}\n\tbefore_range: {before_range}\n\tin_range: {in_range}");
         if before_range || in_range {
-            // println!("\tAdding {dir:?}");
             add_dir(dir);
         }
         if before_range {
-            continue;
+            return next;
         }
         if dir != Dir::Down && dir != Dir::Up {
-            // println!("\tAdding Up and Down");
             add_dir(Dir::Up);
             add_dir(Dir::Down);
         }
         if dir != Dir::Left && dir != Dir::Right {
-            // println!("\tAdding Left and Right");
             add_dir(Dir::Right);
             add_dir(Dir::Left);
         }
-    }
+        next
+    };
+
+    aoc_path::dijkstra(starts, successors, |&(point, _, run)| {
+        point == finish && run >= min
+    })
+    .context("The factory can't be reached")
 }
@@ -0,0 +1,79 @@
+use aoc_core::Solution;
+
+use dijkstra::dijkstra;
+
+mod dijkstra;
+mod point;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn day(&self) -> u8 {
+        17
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> anyhow::Result<usize> {
+    Ok(dijkstra(input, 0, 3)?.cost)
+}
+pub fn part_two(input: &str) -> anyhow::Result<usize> {
+    Ok(dijkstra(input, 4, 10)?.cost)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = {
+        "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT).unwrap(), 102);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(INPUT).unwrap(), 94);
+    }
+
+    #[test]
+    fn crucible_path() {
+        let map = dijkstra::parse_map(INPUT).unwrap();
+        let path = dijkstra(INPUT, 0, 3).unwrap();
+        let (first, last) = (path.states[0].0, path.states.last().unwrap().0);
+        assert_eq!((first.x, first.y), (0, 0));
+        assert_eq!((last.x, last.y), (map.width() - 1, map.height() - 1));
+        let heat_loss: usize = path.states[1..]
+            .iter()
+            .map(|(p, _, _)| map[(p.x, p.y)])
+            .sum();
+        assert_eq!(heat_loss, path.cost);
+    }
+
+    #[test]
+    fn bad_map() {
+        let error = part_one("241\n3x5").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid heat loss 'x'");
+        assert!(part_one("241\n32").is_err());
+    }
+}
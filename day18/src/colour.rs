@@ -0,0 +1,85 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+
+use crate::lagoon::Direction;
+
+/// The colour of a stretch of trench, written as `#rrggbb`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    /// Reads the colour the way the elves meant it: the first five hex digits
+    /// are a distance and the last one is a direction
+    pub fn instruction(&self) -> anyhow::Result<(Direction, i64)> {
+        let value = u32::from_be_bytes([0, self.r, self.g, self.b]);
+        let dir = match value & 0xf {
+            0 => Direction::Right,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            3 => Direction::Up,
+            d => return Err(anyhow!("Invalid direction {d} from hash {self}")),
+        };
+        Ok((dir, i64::from(value >> 4)))
+    }
+}
+
+impl FromStr for Colour {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hash = s.trim_matches(&['(', ')'][..]);
+        let digits = hash
+            .strip_prefix('#')
+            .filter(|d| d.len() == 6 && d.is_ascii())
+            .ok_or(anyhow!("Invalid hash: {hash}"))?;
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16);
+        Ok(Self {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        })
+    }
+}
+
+impl Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_colour() {
+        let colour: Colour = "(#70c710)".parse().unwrap();
+        assert_eq!(
+            colour,
+            Colour {
+                r: 0x70,
+                g: 0xc7,
+                b: 0x10
+            }
+        );
+        assert_eq!(colour.to_string(), "#70c710");
+        assert_eq!(colour.instruction().unwrap(), (Direction::Right, 461937));
+        assert_eq!(
+            "#0dc571".parse::<Colour>().unwrap().instruction().unwrap(),
+            (Direction::Down, 56407)
+        );
+
+        assert!("(#70c71)".parse::<Colour>().is_err());
+        assert!("70c710".parse::<Colour>().is_err());
+        assert!("#70c7zz".parse::<Colour>().is_err());
+        assert!("#70c714".parse::<Colour>().unwrap().instruction().is_err());
+    }
+}
@@ -1,11 +1,42 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{anyhow, Context};
 
+use crate::colour::Colour;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A corner of the trench, along with the colour of the stretch of trench
+/// dug to reach it. The default is the start of the dig, where the plan has
+/// to end up again.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Vertex {
-    x: i64,
-    y: i64,
+    pub x: i64,
+    pub y: i64,
+    pub colour: Colour,
+}
+
+impl Vertex {
+    fn position(&self) -> (i64, i64) {
+        (self.x, self.y)
+    }
+
+    /// Digs `count` metres in `dir` from this vertex
+    fn dig(&self, dir: Direction, count: i64, colour: Colour) -> Self {
+        let (x, y) = match dir {
+            Direction::Up => (self.x, self.y - count),
+            Direction::Down => (self.x, self.y + count),
+            Direction::Left => (self.x - count, self.y),
+            Direction::Right => (self.x + count, self.y),
+        };
+        Self { x, y, colour }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -13,58 +44,123 @@ pub struct Lagoon {
     verts: Box<[Vertex]>,
 }
 
+/// How many cubic metres of a lagoon are inside the trench, and how many are
+/// the trench itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+    pub interior: i64,
+    pub boundary: i64,
+}
+
+impl Geometry {
+    pub fn total(&self) -> i64 {
+        self.interior + self.boundary
+    }
+}
+
 impl Lagoon {
-    pub fn area(&self) -> i64 {
-        if self.verts.len() < 3 {
-            return 0;
+    /// Total number of cubic metres dug out, trench and interior together
+    pub fn area(&self) -> anyhow::Result<i64> {
+        Ok(self.geometry()?.total())
+    }
+
+    /// Treats the centre of each dug cell as a point on a lattice, so the
+    /// trench is a polygon through the vertices. Pick's theorem says its area
+    /// is `interior + boundary / 2 - 1`, where `interior` and `boundary` count
+    /// the lattice points inside and on the polygon, which are the cells we
+    /// want. Fails if the trench touches or crosses itself, as then it isn't a
+    /// simple polygon and the theorem doesn't apply.
+    pub fn geometry(&self) -> anyhow::Result<Geometry> {
+        let edges: Vec<_> = self.edges().collect();
+        if edges.is_empty() {
+            return Ok(Geometry {
+                interior: 0,
+                boundary: 0,
+            });
         }
+        check_edges(&edges)?;
 
-        let first = self.verts.first().expect("size > 2");
-        let last = self.verts.last().expect("size > 2");
-        let sum: i64 = self
-            .verts
-            .windows(2)
-            .map(|s| {
-                (s[0].x * s[1].y) - (s[0].y * s[1].x)
-                    + (s[0].x.abs_diff(s[1].x) + s[0].y.abs_diff(s[1].y)) as i64
-            })
+        // Shoelace formula, which is negative for anticlockwise plans
+        let twice_area = edges
+            .iter()
+            .map(|(a, b)| a.x * b.y - a.y * b.x)
             .sum::<i64>()
-            + (first.y * last.x)
-            - (first.x * last.y)
-            + (last.x.abs_diff(first.x) + last.y.abs_diff(first.y)) as i64;
-        sum / 2 + 1 // not sure why I need this + 1
+            .abs();
+        let boundary = edges
+            .iter()
+            .map(|(a, b)| a.x.abs_diff(b.x) + a.y.abs_diff(b.y))
+            .sum::<u64>() as i64;
+        Ok(Geometry {
+            interior: (twice_area - boundary) / 2 + 1,
+            boundary,
+        })
     }
 
-    pub fn from_hashes(input: &str) -> anyhow::Result<Self> {
-        let vert_count = input.lines().count();
-        let mut verts = Vec::with_capacity(vert_count);
-        let mut prev = Vertex { x: 0, y: 0 };
-        for l in input.lines() {
-            let mut parts = l.split_whitespace();
-            let hash = parts
-                .nth(2)
-                .context("Dig plan {l} is missing a hash")?
-                .trim_matches(&['(', ')'][..]);
-            if !hash.starts_with('#') || hash.len() != 7 {
-                return Err(anyhow!("Invalid hash: {hash}"));
-            }
-            let dir = u8::from_str_radix(&hash[6..], 16)?;
-            let count = i64::from_str_radix(&hash[1..6], 16)?;
-
-            let mut next = prev;
-            match dir {
-                0 => next.x += count,
-                1 => next.y += count,
-                2 => next.x -= count,
-                3 => next.y -= count,
-                _ => return Err(anyhow!("Invalid direction {dir} from hash {hash}")),
-            }
+    /// Pairs of consecutive vertices, including the last back to the first,
+    /// leaving out any that don't move
+    pub fn edges(&self) -> impl Iterator<Item = (Vertex, Vertex)> + '_ {
+        self.verts
+            .iter()
+            .zip(self.verts.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+            .filter(|(a, b)| a.position() != b.position())
+    }
+
+    /// Digs each (direction, distance, colour) step in turn from the start,
+    /// which the plan has to end up back at
+    fn dig_steps(
+        steps: impl Iterator<Item = anyhow::Result<(Direction, i64, Colour)>>,
+    ) -> anyhow::Result<Self> {
+        let mut verts = Vec::with_capacity(steps.size_hint().0);
+        let mut prev = Vertex::default();
+        for step in steps {
+            let (dir, count, colour) = step?;
+            let next = prev.dig(dir, count, colour);
             verts.push(next);
             prev = next;
         }
-        Ok(Lagoon {
-            verts: verts.into(),
-        })
+        match verts.last() {
+            Some(&Vertex { x, y, .. }) if (x, y) != (0, 0) => Err(anyhow!(
+                "Dig plan ends at ({x}, {y}) instead of where it started"
+            )),
+            _ => Ok(Lagoon {
+                verts: verts.into(),
+            }),
+        }
+    }
+
+    /// Parses the plan with the distances and directions taken from the
+    /// colours instead. Only the colour at the end of each line is read, so
+    /// the first two columns don't have to make a sensible plan.
+    pub fn from_hashes(input: &str) -> anyhow::Result<Self> {
+        Self::dig_steps(input.lines().map(|l| {
+            let colour: Colour = l
+                .split_whitespace()
+                .next_back()
+                .context(format!("Dig plan {l} is missing color"))?
+                .parse()?;
+            let (dir, count) = colour.instruction()?;
+            Ok((dir, count, colour))
+        }))
+    }
+
+    /// The lagoon the elves actually meant, where each stretch of trench is
+    /// dug according to its colour instead of the plan's first two columns.
+    /// The stretches keep their colours.
+    pub fn decode_colours(&self) -> anyhow::Result<Self> {
+        Self::dig_steps(self.verts.iter().map(|v| {
+            let (dir, count) = v.colour.instruction()?;
+            Ok((dir, count, v.colour))
+        }))
+    }
+
+    /// How much trench was dug in each colour
+    pub fn lengths_by_colour(&self) -> HashMap<Colour, i64> {
+        let mut lengths = HashMap::new();
+        for (a, b) in self.edges() {
+            *lengths.entry(b.colour).or_default() += (a.x.abs_diff(b.x) + a.y.abs_diff(b.y)) as i64;
+        }
+        lengths
     }
 }
 
@@ -72,10 +168,7 @@ impl FromStr for Lagoon {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let vert_count = s.lines().count();
-        let mut verts = Vec::with_capacity(vert_count);
-        let mut prev = Vertex { x: 0, y: 0 };
-        for l in s.lines() {
+        Self::dig_steps(s.lines().map(|l| {
             let mut parts = l.split_whitespace();
             let dir = parts
                 .next()
@@ -85,30 +178,61 @@ impl FromStr for Lagoon {
                 .context(format!("Dig plan {l} is missing distance"))?
                 .parse()
                 .context(format!("Dig plan {l} has invalid distance"))?;
-            let _ = parts
+            let colour: Colour = parts
                 .next()
-                .context(format!("Dig plan {l} is missing color"))?;
+                .context(format!("Dig plan {l} is missing color"))?
+                .parse()?;
 
             if parts.next().is_some() {
                 return Err(anyhow!("Dig plan {l} has extra information"));
             }
 
-            let mut next = prev;
-            match dir {
-                "U" => next.y -= count,
-                "D" => next.y += count,
-                "L" => next.x -= count,
-                "R" => next.x += count,
+            let dir = match dir {
+                "U" => Direction::Up,
+                "D" => Direction::Down,
+                "L" => Direction::Left,
+                "R" => Direction::Right,
                 d => return Err(anyhow!("Invalid direction {d} in dig plan '{l}'")),
             };
-            verts.push(next);
-            prev = next;
-        }
+            Ok((dir, count, colour))
+        }))
+    }
+}
 
-        Ok(Lagoon {
-            verts: verts.into(),
-        })
+/// Checks that the edges are all horizontal or vertical, and only meet where
+/// one edge leads on to the next
+fn check_edges(edges: &[(Vertex, Vertex)]) -> anyhow::Result<()> {
+    if let Some((a, b)) = edges.iter().find(|(a, b)| a.x != b.x && a.y != b.y) {
+        return Err(anyhow!(
+            "Edge from ({}, {}) to ({}, {}) isn't horizontal or vertical",
+            a.x,
+            a.y,
+            b.x,
+            b.y
+        ));
+    }
+    let bounds =
+        |(a, b): &(Vertex, Vertex)| (a.x.min(b.x), a.x.max(b.x), a.y.min(b.y), a.y.max(b.y));
+    for (i, e1) in edges.iter().enumerate() {
+        let (x0, x1, y0, y1) = bounds(e1);
+        for (j, e2) in edges.iter().enumerate().skip(i + 1) {
+            let (u0, u1, v0, v1) = bounds(e2);
+            let overlap = (x0.max(u0), x1.min(u1), y0.max(v0), y1.min(v1));
+            if overlap.0 > overlap.1 || overlap.2 > overlap.3 {
+                continue;
+            }
+            let adjacent = j == i + 1 || (i == 0 && j == edges.len() - 1);
+            let single_point = overlap.0 == overlap.1 && overlap.2 == overlap.3;
+            if !(adjacent && single_point) {
+                return Err(anyhow!(
+                    "Dig plan crosses itself at ({}, {}), on edges {i} and {j}",
+                    overlap.0,
+                    overlap.2
+                ));
+            }
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -117,7 +241,11 @@ mod tests {
 
     macro_rules! vert {
         ($x: expr, $y: expr) => {
-            Vertex { x: $x, y: $y }
+            Vertex {
+                x: $x,
+                y: $y,
+                colour: Colour::default(),
+            }
         };
     }
 
@@ -129,7 +257,7 @@ mod tests {
         let lagoon = Lagoon {
             verts: vec![vert!(0, 0), vert!(2, 0), vert!(2, 2), vert!(0, 2)].into(),
         };
-        assert_eq!(lagoon.area(), 9);
+        assert_eq!(lagoon.area().unwrap(), 9);
 
         // ###
         // ###
@@ -145,7 +273,7 @@ mod tests {
             ]
             .into(),
         };
-        assert_eq!(l1.area(), 8);
+        assert_eq!(l1.area().unwrap(), 8);
 
         // #######
         // #######
@@ -176,6 +304,84 @@ mod tests {
             ]
             .into(),
         };
-        assert_eq!(l2.area(), 62);
+        assert_eq!(l2.area().unwrap(), 62);
+    }
+
+    #[test]
+    fn interior_and_boundary() {
+        // ####
+        // #..#
+        // #..#
+        // ####
+        let lagoon: Lagoon = "R 3 (#000000)\nD 3 (#000000)\nL 3 (#000000)\nU 3 (#000000)"
+            .parse()
+            .unwrap();
+        let geometry = lagoon.geometry().unwrap();
+        assert_eq!(geometry.interior, 4);
+        assert_eq!(geometry.boundary, 12);
+        assert_eq!(geometry.total(), 16);
+
+        // Same again, but dug anticlockwise
+        let lagoon: Lagoon = "D 3 (#000000)\nR 3 (#000000)\nU 3 (#000000)\nL 3 (#000000)"
+            .parse()
+            .unwrap();
+        assert_eq!(lagoon.geometry().unwrap(), geometry);
+    }
+
+    #[test]
+    fn invalid_plans() {
+        assert!("R 3 (#000000)\nD 3 (#000000)\nL 2 (#000000)"
+            .parse::<Lagoon>()
+            .is_err());
+
+        // Cuts back across the first edge on the way round
+        let crossing = Lagoon {
+            verts: vec![
+                vert!(0, 0),
+                vert!(2, 0),
+                vert!(2, 2),
+                vert!(1, 2),
+                vert!(1, -1),
+                vert!(0, -1),
+            ]
+            .into(),
+        };
+        assert_eq!(
+            crossing.area().unwrap_err().to_string(),
+            "Dig plan crosses itself at (1, 0), on edges 0 and 3"
+        );
+
+        // Doubling back along the trench
+        let back: Lagoon = "R 3 (#000000)\nL 3 (#000000)".parse().unwrap();
+        assert!(back.geometry().is_err());
+    }
+
+    #[test]
+    fn one_plan_both_ways() {
+        let lagoon: Lagoon = crate::tests::INPUT.parse().unwrap();
+        assert_eq!(lagoon.area().unwrap(), 62);
+        assert_eq!(
+            lagoon.decode_colours().unwrap().area().unwrap(),
+            952408144115
+        );
+
+        let lengths = lagoon.lengths_by_colour();
+        assert_eq!(lengths.len(), 14);
+        assert_eq!(lengths[&"#70c710".parse().unwrap()], 6);
+        assert_eq!(lengths.values().sum::<i64>(), 38);
+        // Decoding keeps the colours, but measures the stretches out properly
+        let decoded = lagoon.decode_colours().unwrap().lengths_by_colour();
+        assert_eq!(decoded[&"#caa171".parse().unwrap()], 0xcaa17);
+    }
+
+    #[test]
+    fn hashes_only_need_colours() {
+        // Every line says R 1, which never gets back to the start, but the
+        // colours make a 2x2 square
+        let input = "R 1 (#000010)\nR 1 (#000011)\nR 1 (#000012)\nR 1 (#000013)";
+        assert!(input.parse::<Lagoon>().is_err());
+        assert_eq!(Lagoon::from_hashes(input).unwrap().area().unwrap(), 4);
+        assert!(Lagoon::from_hashes("R 1 (#000010)").is_err());
+        assert!(Lagoon::from_hashes("R 1\nL 1").is_err());
     }
 }
@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use aoc_core::Solution;
+
+use crate::lagoon::Lagoon;
+
+pub mod colour;
+pub mod lagoon;
+pub mod render;
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u8 {
+        18
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> anyhow::Result<i64> {
+    let lagoon = Lagoon::from_str(input)?;
+    lagoon.area()
+}
+pub fn part_two(input: &str) -> anyhow::Result<i64> {
+    let lagoon = Lagoon::from_hashes(input)?;
+
+    lagoon.area()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(62, part_one(INPUT).unwrap());
+    }
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(952408144115, part_two(INPUT).unwrap());
+    }
+}
@@ -0,0 +1,217 @@
+use std::fmt::Write;
+
+use aoc_grid::Grid;
+
+use crate::{
+    colour::Colour,
+    lagoon::{Lagoon, Vertex},
+};
+
+/// What a cell, or block of cells, of the ground around a lagoon looks like
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ground {
+    Level,
+    /// The colour of the stretch of trench dug here. Where two stretches meet
+    /// at a corner it's the colour of the later one.
+    Trench(Colour),
+    Interior,
+}
+
+/// Inclusive range of coordinates shown as one row or column
+type Band = (i64, i64);
+
+impl Lagoon {
+    /// One cell per cubic metre, covering everything from the top left to the
+    /// bottom right of the trench
+    pub fn ground(&self) -> Grid<Ground> {
+        let full = |coords: Vec<i64>| {
+            let min = coords.iter().copied().min().unwrap_or(0);
+            let max = coords.iter().copied().max().unwrap_or(0);
+            (min..=max).map(|c| (c, c)).collect::<Vec<Band>>()
+        };
+        let (xs, ys) = self.coordinates();
+        self.ground_in(&full(xs), &full(ys))
+    }
+
+    /// Like `ground`, but only the rows and columns with a vertex on them are
+    /// kept. Each gap between them is squashed down to a single row or
+    /// column, which is all it needs since nothing changes along it. This
+    /// keeps the plans decoded by `from_hashes` down to a sensible size.
+    pub fn compressed_ground(&self) -> Grid<Ground> {
+        let compress = |mut coords: Vec<i64>| {
+            coords.sort_unstable();
+            coords.dedup();
+            let mut bands = Vec::with_capacity(coords.len() * 2);
+            for pair in coords.windows(2) {
+                bands.push((pair[0], pair[0]));
+                if pair[1] - pair[0] > 1 {
+                    bands.push((pair[0] + 1, pair[1] - 1));
+                }
+            }
+            bands.extend(coords.last().map(|&c| (c, c)));
+            bands
+        };
+        let (xs, ys) = self.coordinates();
+        self.ground_in(&compress(xs), &compress(ys))
+    }
+
+    /// The dug out cells as `#`, and the rest as `.`
+    pub fn render(&self) -> String {
+        self.ground().render(|g| match g {
+            Ground::Level => '.',
+            Ground::Trench(_) | Ground::Interior => '#',
+        })
+    }
+
+    /// A plain text PGM image of the compressed ground, with the trench in
+    /// black and the interior in grey
+    pub fn to_pgm(&self) -> String {
+        let ground = self.compressed_ground();
+        let mut out = format!("P2\n{} {}\n255\n", ground.width(), ground.height());
+        for row in ground.rows() {
+            let shades: Vec<_> = row
+                .iter()
+                .map(|g| match g {
+                    Ground::Level => "255",
+                    Ground::Trench(_) => "0",
+                    Ground::Interior => "128",
+                })
+                .collect();
+            writeln!(out, "{}", shades.join(" ")).expect("Writing to a String can't fail");
+        }
+        out
+    }
+
+    /// A plain text PPM image of the compressed ground, with the trench in
+    /// the colours it was dug in and the interior in grey
+    pub fn to_ppm(&self) -> String {
+        let ground = self.compressed_ground();
+        let mut out = format!("P3\n{} {}\n255\n", ground.width(), ground.height());
+        for row in ground.rows() {
+            let pixels: Vec<_> = row
+                .iter()
+                .map(|g| match g {
+                    Ground::Level => "255 255 255".to_owned(),
+                    Ground::Trench(Colour { r, g, b }) => format!("{r} {g} {b}"),
+                    Ground::Interior => "128 128 128".to_owned(),
+                })
+                .collect();
+            writeln!(out, "{}", pixels.join(" ")).expect("Writing to a String can't fail");
+        }
+        out
+    }
+
+    fn coordinates(&self) -> (Vec<i64>, Vec<i64>) {
+        self.edges()
+            .flat_map(|(a, b)| [a, b])
+            .map(|Vertex { x, y, .. }| (x, y))
+            .unzip()
+    }
+
+    /// Works out the ground for each block of cells, with one column for
+    /// each of the `xs` bands and one row for each of the `ys` bands. The
+    /// trench can only start or turn at the edge of a band, so all of the
+    /// cells in a block look the same.
+    fn ground_in(&self, xs: &[Band], ys: &[Band]) -> Grid<Ground> {
+        let mut ground = Grid::filled(xs.len(), ys.len(), Ground::Level);
+        let band = |bands: &[Band], c: i64| bands.partition_point(|b| b.1 < c);
+        for (a, b) in self.edges() {
+            for x in band(xs, a.x.min(b.x))..=band(xs, a.x.max(b.x)) {
+                for y in band(ys, a.y.min(b.y))..=band(ys, a.y.max(b.y)) {
+                    ground[(x, y)] = Ground::Trench(b.colour);
+                }
+            }
+        }
+
+        // A cell is inside if there's an odd number of trench walls to the
+        // left of it. Walls count from their top end up to just before their
+        // bottom end, so that a corner isn't counted twice.
+        let walls: Vec<_> = self
+            .edges()
+            .filter(|(a, b)| a.x == b.x)
+            .map(|(a, b)| (a.x, a.y.min(b.y), a.y.max(b.y)))
+            .collect();
+        for (y, &(row, _)) in ys.iter().enumerate() {
+            let mut crossings: Vec<i64> = walls
+                .iter()
+                .filter(|&&(_, top, bottom)| top <= row && row < bottom)
+                .map(|&(x, ..)| x)
+                .collect();
+            crossings.sort_unstable();
+            for (x, &(column, _)) in xs.iter().enumerate() {
+                let left = crossings.partition_point(|&c| c < column);
+                if ground[(x, y)] == Ground::Level && left % 2 == 1 {
+                    ground[(x, y)] = Ground::Interior;
+                }
+            }
+        }
+        ground
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_example() {
+        let lagoon: Lagoon = crate::tests::INPUT.parse().unwrap();
+        assert_eq!(
+            lagoon.render(),
+            "#######
+#######
+#######
+..#####
+..#####
+#######
+#####..
+#######
+.######
+.######"
+        );
+        let ground = lagoon.ground();
+        assert_eq!(ground[(1, 1)], Ground::Interior);
+        assert!(matches!(ground[(0, 0)], Ground::Trench(_)));
+        assert_eq!(ground[(0, 3)], Ground::Level);
+    }
+
+    #[test]
+    fn compressed() {
+        let lagoon = Lagoon::from_hashes(crate::tests::INPUT).unwrap();
+        let ground = lagoon.compressed_ground();
+        let cells = ground.iter().count();
+        assert!(cells < 1000, "{cells} cells");
+
+        let pgm = lagoon.to_pgm();
+        let mut lines = pgm.lines();
+        assert_eq!(lines.next(), Some("P2"));
+        assert_eq!(
+            lines.next(),
+            Some(format!("{} {}", ground.width(), ground.height()).as_str())
+        );
+        assert_eq!(lines.next(), Some("255"));
+        assert_eq!(lines.count(), ground.height());
+
+        let ppm = lagoon.to_ppm();
+        assert!(ppm.starts_with(&format!("P3\n{} {}\n", ground.width(), ground.height())));
+        // The first cell is the corner between #7a21e3 and #70c710
+        assert!(ppm.lines().nth(3).unwrap().starts_with("112 199 16 "));
+    }
+
+    #[test]
+    fn compressed_square() {
+        let lagoon: Lagoon = "R 10 (#000000)\nD 10 (#000000)\nL 10 (#000000)\nU 10 (#000000)"
+            .parse()
+            .unwrap();
+        let ground = lagoon.compressed_ground();
+        assert_eq!(
+            ground.render(|g| match g {
+                Ground::Level => '.',
+                Ground::Trench(_) => '#',
+                Ground::Interior => 'o',
+            }),
+            "###\n#o#\n###"
+        );
+        assert_eq!(lagoon.ground().width(), 11);
+    }
+}
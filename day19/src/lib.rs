@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Solution;
+use part::{Categories, Part};
+use program::Program;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use validate::validate;
+use workflow::{Ranges, Workflow, RATINGS};
+
+pub mod part;
+pub mod program;
+pub mod validate;
+pub mod workflow;
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn day(&self) -> u8 {
+        19
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let (wkflw, parts) = input
+        .split_once("\n\n")
+        .context("Missing divider for workflows and parts")?;
+    let categories = Categories::from_parts(parts)?;
+    let program = Program::compile(&parse_workflows(wkflw, &categories)?)?;
+
+    let parts = parts
+        .lines()
+        .map(|l| Part::parse(l, &categories))
+        .collect::<Result<Vec<Part>>>()?;
+
+    #[cfg(feature = "parallel")]
+    let parts = parts.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let parts = parts.into_iter();
+    Ok(parts
+        .filter(|p| program.accepts(p))
+        .map(|part| part.total())
+        .sum())
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    let (wkflw, parts) = input
+        .split_once("\n\n")
+        .context("Missing divider for workflows and parts")?;
+    let categories = Categories::from_parts(parts)?;
+    let workflow_map = parse_workflows(wkflw, &categories)?;
+
+    let starter = &workflow_map["in"];
+    let ranges = Ranges::filled(categories.names().len(), RATINGS);
+
+    starter.eval_range(&workflow_map, ranges)
+}
+
+/// Parses the workflows by name, failing if any of them can't be evaluated
+pub fn parse_workflows(input: &str, categories: &Categories) -> Result<HashMap<String, Workflow>> {
+    let mut workflow_map = HashMap::new();
+    for l in input.lines() {
+        let workflow = Workflow::parse(l, categories)?;
+        if workflow_map.contains_key(&workflow.name) {
+            return Err(anyhow!(
+                "Workflow {} is defined more than once",
+                workflow.name
+            ));
+        }
+        workflow_map.insert(workflow.name.clone(), workflow);
+    }
+    if let Some(error) = validate(&workflow_map, RATINGS)
+        .into_iter()
+        .find(|e| e.is_fatal())
+    {
+        return Err(error.into());
+    }
+    Ok(workflow_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const INPUT: &str = {
+        "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT).unwrap(), 19114);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_matches_serial() {
+        let (workflows, parts) = INPUT.split_once("\n\n").unwrap();
+        let categories = Categories::from_parts(parts).unwrap();
+        let program = Program::compile(&parse_workflows(workflows, &categories).unwrap()).unwrap();
+        let serial: usize = parts
+            .lines()
+            .map(|l| Part::parse(l, &categories).unwrap())
+            .filter(|p| program.accepts(p))
+            .map(|p| p.total())
+            .sum();
+        assert_eq!(part_one(INPUT).unwrap(), serial);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(INPUT).unwrap(), 167409079868000);
+    }
+    #[test]
+    fn invalid_workflows() {
+        assert_eq!(
+            parse_workflows("in{x<10:px,A}", &Categories::default())
+                .unwrap_err()
+                .to_string(),
+            "Rule 0 of in sends parts to undefined workflow px"
+        );
+        assert!(parse_workflows("in{x<10:A,R}\nin{A}", &Categories::default()).is_err());
+        assert!(part_two("in{x<10:in,A}\n\n").is_err());
+    }
+    #[test]
+    fn many_categories() {
+        let five = "in{a>1:A,R}\n\n{a=1,b=1,c=1,d=1,e=1}";
+        assert_eq!(part_two(five).unwrap(), 3999 * 4000usize.pow(4));
+        let six = "in{a>1:A,R}\n\n{a=1,b=1,c=1,d=1,e=1,f=1}";
+        assert_eq!(
+            part_two(six).unwrap_err().to_string(),
+            "Too many parts in the ranges to count"
+        );
+        assert_eq!(part_one(six).unwrap(), 0);
+    }
+
+    #[test]
+    fn custom_categories() {
+        let input =
+            "in{cool>=10:A,shiny==1:A,R}\n\n{cool=10,shiny=0}\n{cool=9,shiny=1}\n{cool=9,shiny=0}";
+        assert_eq!(part_one(input).unwrap(), 20);
+        // Everything with cool of at least 10, plus shiny at 1 with cool below
+        assert_eq!(part_two(input).unwrap(), 3991 * 4000 + 9);
+        assert!(part_one("in{x<10:A,R}\n\n{cool=1}").is_err());
+        assert!(part_one("in{cool<10:A,R}\n\n{cool=1}\n{cool=1,shiny=2}").is_err());
+    }
+}
@@ -1,48 +1,98 @@
-use std::str::FromStr;
+use anyhow::{anyhow, Context, Result};
 
-use anyhow::{anyhow, Context, Error};
+/// The names of the categories parts are rated in, in the order a part
+/// stores its values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Categories {
+    names: Box<[String]>,
+}
+
+impl Default for Categories {
+    /// The puzzle's x, m, a and s
+    fn default() -> Self {
+        Self {
+            names: ["x", "m", "a", "s"].map(str::to_owned).into(),
+        }
+    }
+}
+
+impl Categories {
+    /// The categories are declared by the first part, in the order it lists
+    /// them. Without any parts, these are the default x, m, a and s.
+    pub fn from_parts(parts: &str) -> Result<Self> {
+        let Some(first) = parts.lines().find(|l| !l.trim().is_empty()) else {
+            return Ok(Self::default());
+        };
+        let mut names: Vec<String> = Vec::new();
+        for (key, _) in pairs(first) {
+            let key = key?;
+            if names.iter().any(|n| n == key) {
+                return Err(anyhow!("Multiple values for the same category: {first}"));
+            }
+            names.push(key.to_owned());
+        }
+        Ok(Self {
+            names: names.into(),
+        })
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+/// The `key=value` pairs of a part, with the braces stripped
+fn pairs(part: &str) -> impl Iterator<Item = (Result<&str>, &str)> {
+    part.trim_matches(&['{', '}'][..])
+        .split(',')
+        .map(str::trim)
+        .map(move |kv_pair| match kv_pair.split_once('=') {
+            Some((key, value)) => (Ok(key.trim()), value.trim()),
+            None => (Err(anyhow!("Invalid key-value pair in part {part}")), ""),
+        })
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Part {
-    pub x: usize,
-    pub m: usize,
-    pub a: usize,
-    pub s: usize,
+    /// One rating for each of the categories
+    values: Box<[usize]>,
 }
 
-impl FromStr for Part {
-    type Err = Error;
-
-    fn from_str(part: &str) -> std::result::Result<Self, Self::Err> {
-        let input = part.trim_matches(&['{', '}'][..]);
-        let (mut x, mut m, mut a, mut s) = (None, None, None, None);
-        let assign = |holder: &mut Option<usize>, value: usize| {
-            if holder.is_some() {
+impl Part {
+    /// Parses a part like `{x=787,m=2655,a=1222,s=2876}`, which needs a value
+    /// for every one of `categories` and nothing else
+    pub fn parse(part: &str, categories: &Categories) -> Result<Self> {
+        let mut values = vec![None; categories.names().len()];
+        for (key, value) in pairs(part) {
+            let key = key?;
+            let value = value.parse::<usize>()?;
+            let i = categories
+                .index(key)
+                .ok_or(anyhow!("Unknown key '{key}' in part"))?;
+            if values[i].replace(value).is_some() {
                 return Err(anyhow!("Multiple values for the same category: {part}"));
             }
-            *holder = Some(value);
-            Ok(())
-        };
-
-        for kv_pair in input.split(',').map(str::trim) {
-            let (key, value) = kv_pair
-                .split_once('=')
-                .context("Invalid key-value pair in part {part}")?;
-            let value = value.trim().parse::<usize>()?;
-            match key.trim() {
-                "x" => assign(&mut x, value)?,
-                "m" => assign(&mut m, value)?,
-                "a" => assign(&mut a, value)?,
-                "s" => assign(&mut s, value)?,
-                _ => return Err(anyhow!("Unknown key '{key}' in part")),
-            };
         }
 
-        let x = x.context("Missing x in part '{part}'")?;
-        let m = m.context("Missing m in part '{part}'")?;
-        let a = a.context("Missing a in part '{part}'")?;
-        let s = s.context("Missing s in part '{part}'")?;
+        let values = values
+            .into_iter()
+            .zip(categories.names())
+            .map(|(v, name)| v.context(format!("Missing {name} in part '{part}'")))
+            .collect::<Result<_>>()?;
+        Ok(Self { values })
+    }
+
+    /// The rating for the category at `index` in the part's `Categories`
+    pub fn get(&self, index: usize) -> usize {
+        self.values[index]
+    }
 
-        Ok(Self { x, m, a, s })
+    /// All of the ratings added together
+    pub fn total(&self) -> usize {
+        self.values.iter().sum()
     }
 }
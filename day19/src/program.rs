@@ -0,0 +1,196 @@
+use std::{collections::HashMap, ops::Range};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    part::Part,
+    workflow::{Condition, RuleAction, Workflow},
+};
+
+/// Where a rule sends a part, with workflow names resolved to indices
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Accept,
+    Reject,
+    Workflow(usize),
+}
+
+/// A workflow's rules, as a range into the program's flat list of rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Block {
+    rules: Range<usize>,
+    fallback: Target,
+}
+
+/// A rule a part went through. `rule` is an index into the workflow's rules,
+/// or `None` for its fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hop {
+    pub workflow: usize,
+    pub rule: Option<usize>,
+}
+
+/// Whether a part was accepted, and how it got there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub accepted: bool,
+    pub path: Vec<Hop>,
+}
+
+/// Workflows compiled once for evaluating lots of parts, so that moving
+/// between workflows is an index rather than a lookup by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    names: Box<[String]>,
+    blocks: Box<[Block]>,
+    rules: Box<[(Condition, Target)]>,
+    start: usize,
+}
+
+impl Program {
+    /// Compiles workflows from [`parse_workflows`](crate::parse_workflows),
+    /// which has already checked them for loops that would never finish.
+    /// Still fails if there's no `in` workflow or a rule sends parts to a
+    /// workflow that doesn't exist.
+    pub fn compile(workflows: &HashMap<String, Workflow>) -> Result<Self> {
+        let mut names: Vec<&String> = workflows.keys().collect();
+        names.sort_unstable();
+        let indices: HashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.as_str(), i))
+            .collect();
+        let target = |action: &RuleAction| -> Result<Target> {
+            Ok(match action {
+                RuleAction::Accept => Target::Accept,
+                RuleAction::Reject => Target::Reject,
+                RuleAction::Move(name) => Target::Workflow(
+                    *indices
+                        .get(name.as_str())
+                        .context(format!("No workflow named {name}"))?,
+                ),
+            })
+        };
+
+        let mut rules = Vec::new();
+        let mut blocks = Vec::with_capacity(names.len());
+        for name in &names {
+            let workflow = &workflows[*name];
+            let first = rules.len();
+            for (condition, (_, action)) in workflow.conditions().zip(workflow.actions()) {
+                rules.push((condition, target(action)?));
+            }
+            blocks.push(Block {
+                rules: first..rules.len(),
+                fallback: target(workflow.fallback())?,
+            });
+        }
+
+        Ok(Self {
+            start: *indices
+                .get("in")
+                .context("No 'in' workflow to start from")?,
+            names: names.into_iter().cloned().collect(),
+            blocks: blocks.into(),
+            rules: rules.into(),
+        })
+    }
+
+    /// The name of the workflow at `index`, as used in a [`Hop`]
+    pub fn name(&self, index: usize) -> &str {
+        &self.names[index]
+    }
+
+    pub fn accepts(&self, part: &Part) -> bool {
+        self.run(part, |_| ())
+    }
+
+    pub fn evaluate(&self, part: &Part) -> Outcome {
+        let mut path = Vec::new();
+        let accepted = self.run(part, |hop| path.push(hop));
+        Outcome { accepted, path }
+    }
+
+    /// Evaluates each part in turn, from a slice or any other source of parts
+    pub fn evaluate_all<'a, I>(&'a self, parts: I) -> impl Iterator<Item = Outcome> + 'a
+    where
+        I: IntoIterator<Item = &'a Part>,
+        I::IntoIter: 'a,
+    {
+        parts.into_iter().map(|p| self.evaluate(p))
+    }
+
+    fn run(&self, part: &Part, mut visit: impl FnMut(Hop)) -> bool {
+        let mut workflow = self.start;
+        loop {
+            let block = &self.blocks[workflow];
+            let rules = &self.rules[block.rules.clone()];
+            let (rule, target) = match rules.iter().position(|(c, _)| c.matches(part)) {
+                Some(i) => (Some(i), rules[i].1),
+                None => (None, block.fallback),
+            };
+            visit(Hop { workflow, rule });
+            match target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(next) => workflow = next,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_workflows, part::Categories, tests::INPUT};
+
+    #[test]
+    fn evaluate_example() {
+        let (workflows, parts) = INPUT.split_once("\n\n").unwrap();
+        let categories = Categories::from_parts(parts).unwrap();
+        let program = Program::compile(&parse_workflows(workflows, &categories).unwrap()).unwrap();
+        let parts: Vec<Part> = parts
+            .lines()
+            .map(|l| Part::parse(l, &categories).unwrap())
+            .collect();
+
+        let accepted: Vec<bool> = program.evaluate_all(&parts).map(|o| o.accepted).collect();
+        assert_eq!(accepted, [true, false, true, false, true]);
+
+        // in -> px -> rfg -> gd -> R
+        let path: Vec<_> = program
+            .evaluate(&parts[1])
+            .path
+            .iter()
+            .map(|h| (program.name(h.workflow), h.rule))
+            .collect();
+        assert_eq!(
+            path,
+            [
+                ("in", Some(0)),
+                ("px", None),
+                ("rfg", Some(0)),
+                ("gd", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn refuses_invalid() {
+        let compile = |lines: &[&str]| {
+            let workflows = lines
+                .iter()
+                .map(|l| Workflow::parse(l, &Categories::default()).unwrap())
+                .map(|w| (w.name.clone(), w))
+                .collect();
+            Program::compile(&workflows)
+        };
+        assert_eq!(
+            compile(&["in{x<10:px,A}"]).unwrap_err().to_string(),
+            "No workflow named px"
+        );
+        assert!(compile(&["px{x<10:A,R}"]).is_err());
+        // Loops are caught when the workflows are parsed
+        assert!(parse_workflows("in{x<10:px,A}\npx{m>5:in,R}", &Categories::default()).is_err());
+    }
+}
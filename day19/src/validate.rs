@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::workflow::{Range, RuleAction, Workflow};
+
+/// A problem with a set of workflows. `rule` is an index into the workflow's
+/// rules, or `None` for its fallback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    MissingStart,
+    UndefinedTarget {
+        workflow: String,
+        rule: Option<usize>,
+        target: String,
+    },
+    /// `cycle` lists the workflows in the loop, starting with the one the
+    /// rule sends parts back to
+    Cycle {
+        workflow: String,
+        rule: Option<usize>,
+        cycle: Vec<String>,
+    },
+    ShadowedRule {
+        workflow: String,
+        rule: Option<usize>,
+    },
+}
+
+impl ValidationError {
+    /// Whether the workflows can't be evaluated at all. A shadowed rule is
+    /// pointless, but harmless.
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, Self::ShadowedRule { .. })
+    }
+}
+
+fn rule_name(rule: &Option<usize>) -> String {
+    match rule {
+        Some(i) => format!("Rule {i}"),
+        None => "The fallback".to_owned(),
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStart => write!(f, "There is no 'in' workflow to start from"),
+            Self::UndefinedTarget {
+                workflow,
+                rule,
+                target,
+            } => write!(
+                f,
+                "{} of {workflow} sends parts to undefined workflow {target}",
+                rule_name(rule)
+            ),
+            Self::Cycle {
+                workflow,
+                rule,
+                cycle,
+            } => write!(
+                f,
+                "{} of {workflow} loops back through {} -> {}",
+                rule_name(rule),
+                cycle.join(" -> "),
+                cycle[0]
+            ),
+            Self::ShadowedRule { workflow, rule } => {
+                write!(f, "{} of {workflow} can never match", rule_name(rule))
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Checks the workflows for a missing entry point, rules sending parts to
+/// workflows that don't exist, loops between workflows and rules that can
+/// never match a part with ratings in `domain`
+pub fn validate(workflows: &HashMap<String, Workflow>, domain: Range) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if !workflows.contains_key("in") {
+        errors.push(ValidationError::MissingStart);
+    }
+
+    let mut names: Vec<&str> = workflows.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    for &name in &names {
+        let workflow = &workflows[name];
+        for (rule, action) in workflow.actions() {
+            if let RuleAction::Move(target) = action {
+                if !workflows.contains_key(target) {
+                    errors.push(ValidationError::UndefinedTarget {
+                        workflow: name.to_owned(),
+                        rule,
+                        target: target.clone(),
+                    });
+                }
+            }
+        }
+        errors.extend(workflow.shadowed_rules(domain).into_iter().map(|rule| {
+            ValidationError::ShadowedRule {
+                workflow: name.to_owned(),
+                rule,
+            }
+        }));
+    }
+
+    // Start from `in` so loops are reported the way parts would run into them
+    let mut visits = HashMap::new();
+    let starts = workflows.get_key_value("in").map(|(n, _)| n.as_str());
+    for name in starts.into_iter().chain(names) {
+        if !visits.contains_key(name) {
+            find_cycles(workflows, name, &mut visits, &mut Vec::new(), &mut errors);
+        }
+    }
+    errors
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+/// Depth first search from `name`, reporting every rule that leads back to a
+/// workflow still on the stack
+fn find_cycles<'a>(
+    workflows: &'a HashMap<String, Workflow>,
+    name: &'a str,
+    visits: &mut HashMap<&'a str, Visit>,
+    stack: &mut Vec<&'a str>,
+    errors: &mut Vec<ValidationError>,
+) {
+    visits.insert(name, Visit::InProgress);
+    stack.push(name);
+    for (rule, action) in workflows[name].actions() {
+        let RuleAction::Move(target) = action else {
+            continue;
+        };
+        if !workflows.contains_key(target) {
+            continue;
+        }
+        match visits.get(target.as_str()) {
+            Some(Visit::Done) => (),
+            Some(Visit::InProgress) => {
+                let start = stack.iter().position(|n| n == target).unwrap();
+                errors.push(ValidationError::Cycle {
+                    workflow: name.to_owned(),
+                    rule,
+                    cycle: stack[start..].iter().map(|n| n.to_string()).collect(),
+                });
+            }
+            None => find_cycles(workflows, target, visits, stack, errors),
+        }
+    }
+    stack.pop();
+    visits.insert(name, Visit::Done);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part::Categories, workflow::RATINGS};
+
+    fn workflows(input: &str) -> HashMap<String, Workflow> {
+        input
+            .lines()
+            .map(|l| Workflow::parse(l, &Categories::default()).unwrap())
+            .map(|w| (w.name.clone(), w))
+            .collect()
+    }
+
+    #[test]
+    fn example_is_valid() {
+        let input = crate::tests::INPUT.split_once("\n\n").unwrap().0;
+        assert_eq!(validate(&workflows(input), RATINGS), vec![]);
+    }
+
+    #[test]
+    fn problems() {
+        let workflows = workflows(
+            "in{x<10:a,x<5:R,b}
+a{m>100:zz,b}
+b{s>10:c,s<20:R,A}
+c{a<4000:b,R}",
+        );
+        assert_eq!(
+            validate(&workflows, RATINGS),
+            vec![
+                ValidationError::UndefinedTarget {
+                    workflow: "a".to_owned(),
+                    rule: Some(0),
+                    target: "zz".to_owned()
+                },
+                ValidationError::ShadowedRule {
+                    workflow: "b".to_owned(),
+                    rule: None
+                },
+                ValidationError::ShadowedRule {
+                    workflow: "in".to_owned(),
+                    rule: Some(1)
+                },
+                ValidationError::Cycle {
+                    workflow: "c".to_owned(),
+                    rule: Some(0),
+                    cycle: vec!["b".to_owned(), "c".to_owned()]
+                },
+            ]
+        );
+        assert_eq!(
+            validate(&workflows, RATINGS)[3].to_string(),
+            "Rule 0 of c loops back through b -> c -> b"
+        );
+    }
+
+    #[test]
+    fn missing_start() {
+        let errors = validate(&workflows("px{a<2006:A,R}"), RATINGS);
+        assert_eq!(errors, vec![ValidationError::MissingStart]);
+        assert!(errors[0].is_fatal());
+    }
+}
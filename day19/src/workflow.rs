@@ -1,14 +1,21 @@
 use anyhow::{anyhow, Context, Error, Result};
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, iter, str::FromStr};
 
-use crate::part::Part;
+use crate::part::{Categories, Part};
 
-type Range = (usize, usize);
+/// Inclusive range of values for one category
+pub type Range = (usize, usize);
+
+/// The ratings a part can have in any category in the puzzle
+pub const RATINGS: Range = (1, 4000);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Comparison {
     Less,
+    LessEq,
     Greater,
+    GreaterEq,
+    Equal,
 }
 
 impl FromStr for Comparison {
@@ -17,12 +24,54 @@ impl FromStr for Comparison {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "<" => Ok(Self::Less),
+            "<=" => Ok(Self::LessEq),
             ">" => Ok(Self::Greater),
+            ">=" => Ok(Self::GreaterEq),
+            "==" => Ok(Self::Equal),
             _ => Err(anyhow!("Unknown comparison {s}")),
         }
     }
 }
 
+impl Comparison {
+    fn test(self, a: usize, b: usize) -> bool {
+        match self {
+            Self::Less => a < b,
+            Self::LessEq => a <= b,
+            Self::Greater => a > b,
+            Self::GreaterEq => a >= b,
+            Self::Equal => a == b,
+        }
+    }
+
+    /// The ranges of values within `domain` that pass the comparison against
+    /// `value`, and the ranges that fail it. Empty ranges are left out.
+    fn ranges(self, value: usize, (low, high): Range) -> (Vec<Range>, Vec<Range>) {
+        let below = value.checked_sub(1).map(|v| (low, v.min(high)));
+        let above = value.checked_add(1).map(|v| (v.max(low), high));
+        let from = Some((value.max(low), high));
+        let to = Some((low, value.min(high)));
+        let (pass, fail) = match self {
+            Self::Less => (vec![below], vec![from]),
+            Self::LessEq => (vec![to], vec![above]),
+            Self::Greater => (vec![above], vec![to]),
+            Self::GreaterEq => (vec![from], vec![below]),
+            Self::Equal => (
+                vec![Some((value.max(low), value.min(high)))],
+                vec![below, above],
+            ),
+        };
+        let non_empty = |ranges: Vec<Option<Range>>| {
+            ranges
+                .into_iter()
+                .flatten()
+                .filter(|(low, high)| low <= high)
+                .collect()
+        };
+        (non_empty(pass), non_empty(fail))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RuleAction {
     Accept,
@@ -42,96 +91,65 @@ impl FromStr for RuleAction {
     }
 }
 
+/// The test a rule makes on a part
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum RuleApply {
-    X,
-    M,
-    A,
-    S,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Rule {
-    apply_to: RuleApply,
+pub struct Condition {
+    /// Index into the `Categories`
+    category: usize,
     comp: Comparison,
     value: usize,
-    action: RuleAction,
 }
 
-impl Rule {
-    fn matches(&self, part: &Part) -> bool {
-        let to_compare = match self.apply_to {
-            RuleApply::X => part.x,
-            RuleApply::M => part.m,
-            RuleApply::A => part.a,
-            RuleApply::S => part.s,
-        };
-
-        match self.comp {
-            Comparison::Less => to_compare < self.value,
-            Comparison::Greater => to_compare > self.value,
-        }
-    }
-
-    fn split_ranges(
-        &self,
-        x: &mut Range,
-        m: &mut Range,
-        a: &mut Range,
-        s: &mut Range,
-    ) -> (Range, Range, Range, Range) {
-        let (mut new_x, mut new_m, mut new_a, mut new_s) = (*x, *m, *a, *s);
-        match self.apply_to {
-            RuleApply::X => new_x = self.split_helper(x),
-            RuleApply::M => new_m = self.split_helper(m),
-            RuleApply::A => new_a = self.split_helper(a),
-            RuleApply::S => new_s = self.split_helper(s),
-        }
-        (new_x, new_m, new_a, new_s)
+impl Condition {
+    pub fn matches(&self, part: &Part) -> bool {
+        self.comp.test(part.get(self.category), self.value)
     }
 
-    fn split_helper(&self, range: &mut Range) -> Range {
-        let send: Range;
-        match self.comp {
-            Comparison::Less => {
-                send = (range.0, self.value - 1);
-                *range = (self.value, range.1);
-            }
-            Comparison::Greater => {
-                send = (self.value + 1, range.1);
-                *range = (range.0, self.value);
-            }
-        }
-        send
+    /// Splits `ranges` into the boxes that match the condition and the boxes
+    /// that don't. An `==` leaves up to two boxes either side of its value.
+    fn split_ranges(&self, ranges: &Ranges) -> (Vec<Ranges>, Vec<Ranges>) {
+        let (pass, fail) = self.comp.ranges(self.value, ranges.get(self.category));
+        let split = |parts: Vec<Range>| {
+            parts
+                .into_iter()
+                .map(|r| ranges.intersect(self.category, r))
+                .filter(|r| !r.is_empty())
+                .collect()
+        };
+        (split(pass), split(fail))
     }
 }
 
-impl FromStr for Rule {
-    type Err = Error;
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    condition: Condition,
+    action: RuleAction,
+}
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+impl Rule {
+    fn parse(s: &str, categories: &Categories) -> Result<Self> {
         let Some((cond, action)) = s.split_once(':') else {
             return Err(anyhow!(
                 "Empty rules must be represented as workflow fallbacks"
             ));
         };
-        if cond.len() < 3 {
-            return Err(anyhow!("Invalid condition {cond}"));
-        }
         let action = RuleAction::from_str(action)?;
-        let comp = Comparison::from_str(&cond[1..2])?;
-        let value = usize::from_str(&cond[2..])?;
-        let apply_to = match &cond[0..1] {
-            "x" => RuleApply::X,
-            "m" => RuleApply::M,
-            "a" => RuleApply::A,
-            "s" => RuleApply::S,
-            cat => return Err(anyhow!("Unknown category for rule condition: {cat}")),
+        let (Some(start), Some(end)) = (cond.find(['<', '>', '=']), cond.rfind(['<', '>', '=']))
+        else {
+            return Err(anyhow!("Invalid condition {cond}"));
         };
+        let comp = Comparison::from_str(&cond[start..=end])?;
+        let value = usize::from_str(&cond[end + 1..])?;
+        let category = categories.index(&cond[..start]).ok_or(anyhow!(
+            "Unknown category for rule condition: {}",
+            &cond[..start]
+        ))?;
         Ok(Self {
-            apply_to,
-            comp,
-            value,
+            condition: Condition {
+                category,
+                comp,
+                value,
+            },
             action,
         })
     }
@@ -147,78 +165,230 @@ pub struct Workflow {
 impl Workflow {
     pub fn apply(&self, part: &Part) -> RuleAction {
         for rule in &self.rules {
-            if rule.matches(part) {
+            if rule.condition.matches(part) {
                 return rule.action.clone();
             }
         }
         self.fallback.clone()
     }
 
-    pub fn eval_range(
+    /// What each rule does, followed by the fallback as rule `None`
+    pub fn actions(&self) -> impl Iterator<Item = (Option<usize>, &RuleAction)> {
+        self.rules
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (Some(i), &r.action))
+            .chain(iter::once((None, &self.fallback)))
+    }
+
+    pub fn fallback(&self) -> &RuleAction {
+        &self.fallback
+    }
+
+    /// The conditions of each rule, in order
+    pub fn conditions(&self) -> impl Iterator<Item = Condition> + '_ {
+        self.rules.iter().map(|r| r.condition)
+    }
+
+    /// Rules that can never match a part with ratings in `domain`, because
+    /// they ask for ratings outside of it or the rules before them already
+    /// take every part they would. The fallback is rule `None`.
+    pub fn shadowed_rules(&self, domain: Range) -> Vec<Option<usize>> {
+        let dimensions = self
+            .rules
+            .iter()
+            .map(|r| r.condition.category + 1)
+            .max()
+            .unwrap_or(0);
+        let mut remaining = vec![Ranges::filled(dimensions, domain)];
+        let mut shadowed = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            let mut matched = false;
+            let mut rest = Vec::new();
+            for ranges in &remaining {
+                let (pass, fail) = rule.condition.split_ranges(ranges);
+                matched |= !pass.is_empty();
+                rest.extend(fail);
+            }
+            if !matched {
+                shadowed.push(Some(i));
+            }
+            remaining = rest;
+        }
+        if remaining.is_empty() {
+            shadowed.push(None);
+        }
+        shadowed
+    }
+
+    /// The number of parts in the given ranges that end up accepted. The
+    /// workflows should have passed [`validate`](crate::validate::validate),
+    /// as a cycle never finishes. Fails if a rule sends parts to a workflow
+    /// that doesn't exist, or if there are too many parts to count.
+    pub fn eval_range(&self, workflows: &HashMap<String, Self>, ranges: Ranges) -> Result<usize> {
+        self.accepted_boxes(workflows, ranges)?
+            .iter()
+            .try_fold(0usize, |total, b| {
+                total
+                    .checked_add(b.ranges.combinations()?)
+                    .context("Too many accepted parts to count")
+            })
+    }
+
+    /// Splits the given ranges into the boxes that end up accepted, starting
+    /// from this workflow. The boxes don't overlap, and each one remembers the
+    /// rules that sent it on its way. Like `eval_range`, this fails on an
+    /// undefined target and expects there to be no cycles.
+    pub fn accepted_boxes(
         &self,
         workflows: &HashMap<String, Self>,
-        mut x: Range,
-        mut m: Range,
-        mut a: Range,
-        mut s: Range,
-    ) -> usize {
-        let mut combos = 0;
+        ranges: Ranges,
+    ) -> Result<Vec<AcceptedBox>> {
+        let mut boxes = Vec::new();
+        self.collect_boxes(workflows, ranges, &mut Vec::new(), &mut boxes)?;
+        Ok(boxes)
+    }
 
-        for rule in &self.rules {
-            let (send_x, send_m, send_a, send_s) =
-                rule.split_ranges(&mut x, &mut m, &mut a, &mut s);
-            match rule.action {
-                RuleAction::Accept => {
-                    let nc = (send_x.1 - send_x.0 + 1)
-                        * (send_m.1 - send_m.0 + 1)
-                        * (send_a.1 - send_a.0 + 1)
-                        * (send_s.1 - send_s.0 + 1);
-                    // println!("\tAdding {nc}");
-                    combos += nc;
-                }
-                RuleAction::Reject => (),
-                RuleAction::Move(ref name) => {
-                    combos += workflows
-                        .get(name)
-                        .unwrap()
-                        .eval_range(workflows, send_x, send_m, send_a, send_s)
+    fn collect_boxes(
+        &self,
+        workflows: &HashMap<String, Self>,
+        ranges: Ranges,
+        path: &mut Vec<Step>,
+        boxes: &mut Vec<AcceptedBox>,
+    ) -> Result<()> {
+        let mut remaining = vec![ranges];
+        for (i, rule) in self.rules.iter().enumerate() {
+            let mut rest = Vec::new();
+            for ranges in remaining {
+                let (pass, fail) = rule.condition.split_ranges(&ranges);
+                for ranges in pass {
+                    self.follow(workflows, &rule.action, Some(i), ranges, path, boxes)?;
                 }
+                rest.extend(fail);
             }
-        }
-        match self.fallback {
-            RuleAction::Accept => {
-                let nc = (x.1 - x.0 + 1) * (m.1 - m.0 + 1) * (a.1 - a.0 + 1) * (s.1 - s.0 + 1);
-                combos += nc
+            if rest.is_empty() {
+                return Ok(());
             }
+            remaining = rest;
+        }
+        for ranges in remaining {
+            self.follow(workflows, &self.fallback, None, ranges, path, boxes)?;
+        }
+        Ok(())
+    }
+
+    /// Sends `ranges` on through `action`, which is rule `rule` of this
+    /// workflow or its fallback
+    fn follow(
+        &self,
+        workflows: &HashMap<String, Self>,
+        action: &RuleAction,
+        rule: Option<usize>,
+        ranges: Ranges,
+        path: &mut Vec<Step>,
+        boxes: &mut Vec<AcceptedBox>,
+    ) -> Result<()> {
+        if ranges.is_empty() || *action == RuleAction::Reject {
+            return Ok(());
+        }
+        path.push(Step {
+            workflow: self.name.clone(),
+            rule,
+        });
+        match action {
+            RuleAction::Accept => boxes.push(AcceptedBox {
+                ranges,
+                path: path.clone(),
+            }),
             RuleAction::Reject => (),
-            RuleAction::Move(ref name) => {
-                combos += workflows
-                    .get(name)
-                    .unwrap()
-                    .eval_range(workflows, x, m, a, s)
-            }
+            RuleAction::Move(name) => workflows
+                .get(name)
+                .context(format!("No workflow named {name}"))?
+                .collect_boxes(workflows, ranges, path, boxes)?,
         }
-        combos
+        path.pop();
+        Ok(())
     }
 }
 
-impl FromStr for Workflow {
-    type Err = Error;
+/// A range for each category, in the same order as the `Categories`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranges(Box<[Range]>);
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+impl Ranges {
+    pub fn filled(dimensions: usize, range: Range) -> Self {
+        Self(vec![range; dimensions].into())
+    }
+
+    pub fn get(&self, category: usize) -> Range {
+        self.0[category]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(|&(low, high)| low > high)
+    }
+
+    /// How many different parts fit in the ranges. Fails if there are too
+    /// many to count, which takes a handful of categories at most.
+    pub fn combinations(&self) -> Result<usize> {
+        if self.is_empty() {
+            return Ok(0);
+        }
+        self.0.iter().try_fold(1usize, |product, &(low, high)| {
+            product
+                .checked_mul(high - low + 1)
+                .context("Too many parts in the ranges to count")
+        })
+    }
+
+    pub fn contains(&self, part: &Part) -> bool {
+        self.0
+            .iter()
+            .enumerate()
+            .all(|(i, &(low, high))| (low..=high).contains(&part.get(i)))
+    }
+
+    /// The same ranges, with `category` cut down to the overlap with `range`
+    fn intersect(&self, category: usize, (low, high): Range) -> Self {
+        let mut ranges = self.clone();
+        let r = &mut ranges.0[category];
+        *r = (r.0.max(low), r.1.min(high));
+        ranges
+    }
+}
+
+/// A rule that a box of parts went through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub workflow: String,
+    /// Index into the workflow's rules, or `None` for its fallback
+    pub rule: Option<usize>,
+}
+
+/// Ranges where every part is accepted, and the route they took to get there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptedBox {
+    pub ranges: Ranges,
+    pub path: Vec<Step>,
+}
+
+impl Workflow {
+    /// Parses a workflow like `px{a<2006:qkq,m>2090:A,rfg}`, where the rules
+    /// can only use the given categories
+    pub fn parse(s: &str, categories: &Categories) -> Result<Self> {
         let Some((name, rules)) = s.trim_end_matches('}').split_once('{') else {
             return Err(anyhow!("Couldn't find name in workflow: {s}"));
         };
         let fallback = RuleAction::from_str(
             rules
                 .split(',')
-                .last()
+                .next_back()
                 .context("Cannot create an empty workflow")?,
         )?;
         let rules = rules
             .split(',')
             .take_while(|r| r.contains(':'))
-            .map(Rule::from_str)
+            .map(|r| Rule::parse(r, categories))
             .collect::<Result<Vec<Rule>, Error>>()?;
 
         Ok(Self {
@@ -228,3 +398,167 @@ impl FromStr for Workflow {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflows() -> HashMap<String, Workflow> {
+        let input = crate::tests::INPUT.split_once("\n\n").unwrap().0;
+        crate::parse_workflows(input, &Categories::default()).unwrap()
+    }
+
+    #[test]
+    fn split_outside_range() {
+        let rule = Rule::parse("x<2000:A", &Categories::default()).unwrap();
+        let ranges = Ranges(vec![(1, 1000), (1, 1), (1, 1), (1, 1)].into());
+        let (pass, fail) = rule.condition.split_ranges(&ranges);
+        assert_eq!(pass, vec![ranges]);
+        assert!(fail.is_empty());
+    }
+
+    #[test]
+    fn comparisons() {
+        let categories = Categories::from_parts("{cool=5,shiny=0}").unwrap();
+        let part = Part::parse("{cool=5,shiny=0}", &categories).unwrap();
+        for (rule, matches) in [
+            ("cool<5:A", false),
+            ("cool<=5:A", true),
+            ("cool>=5:A", true),
+            ("cool==5:A", true),
+            ("shiny==1:A", false),
+            ("shiny<1:A", true),
+        ] {
+            let rule = Rule::parse(rule, &categories).unwrap();
+            assert_eq!(rule.condition.matches(&part), matches, "{rule:?}");
+        }
+        assert!(Rule::parse("x<5:A", &categories).is_err());
+        assert!(Rule::parse("cool=<5:A", &categories).is_err());
+
+        let rule = Rule::parse("shiny==3:A", &categories).unwrap();
+        let (pass, fail) = rule
+            .condition
+            .split_ranges(&Ranges(vec![(1, 10), (1, 10)].into()));
+        assert_eq!(pass, vec![Ranges(vec![(1, 10), (3, 3)].into())]);
+        assert_eq!(
+            fail,
+            vec![
+                Ranges(vec![(1, 10), (1, 2)].into()),
+                Ranges(vec![(1, 10), (4, 10)].into())
+            ]
+        );
+    }
+
+    #[test]
+    fn comparison_edges() {
+        assert_eq!(Comparison::Less.ranges(0, (0, 10)), (vec![], vec![(0, 10)]));
+        assert_eq!(Comparison::Less.ranges(1, RATINGS), (vec![], vec![RATINGS]));
+        assert_eq!(
+            Comparison::Greater.ranges(4000, RATINGS),
+            (vec![], vec![RATINGS])
+        );
+        assert_eq!(
+            Comparison::Equal.ranges(1, RATINGS),
+            (vec![(1, 1)], vec![(2, 4000)])
+        );
+        assert_eq!(
+            Comparison::GreaterEq.ranges(usize::MAX, (0, usize::MAX)),
+            (vec![(usize::MAX, usize::MAX)], vec![(0, usize::MAX - 1)])
+        );
+    }
+
+    #[test]
+    fn shadowed_by_domain() {
+        let shadowed = |s: &str| {
+            Workflow::parse(s, &Categories::default())
+                .unwrap()
+                .shadowed_rules(RATINGS)
+        };
+        assert_eq!(shadowed("in{x<1:A,R}"), vec![Some(0)]);
+        assert_eq!(shadowed("in{x>4000:A,x<=0:A,R}"), vec![Some(0), Some(1)]);
+        assert_eq!(shadowed("in{x<4001:A,R}"), vec![None]);
+        assert_eq!(shadowed("in{x>0:A,R}"), vec![None]);
+        assert_eq!(shadowed("in{x<2:A,x==1:R,A}"), vec![Some(1)]);
+        assert!(shadowed("in{x<2:A,x>3999:A,R}").is_empty());
+    }
+
+    #[test]
+    fn n_dimensional_ranges() {
+        let categories = Categories::from_parts("{a=1,b=1,c=1}").unwrap();
+        let workflows =
+            crate::parse_workflows("in{a==2:R,b>=3:ok,R}\nok{c<=2:A,R}", &categories).unwrap();
+        // a is anything but 2, b is 3 or 4, and c is 1 or 2
+        let ranges = Ranges::filled(3, (1, 4));
+        assert_eq!(
+            workflows["in"]
+                .eval_range(&workflows, ranges.clone())
+                .unwrap(),
+            12
+        );
+        assert_eq!(
+            workflows["in"]
+                .accepted_boxes(&workflows, ranges)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn accepted_boxes() {
+        let workflows = workflows();
+        let boxes = workflows["in"]
+            .accepted_boxes(&workflows, Ranges::filled(4, (1, 4000)))
+            .unwrap();
+        assert_eq!(
+            boxes
+                .iter()
+                .map(|b| b.ranges.combinations().unwrap())
+                .sum::<usize>(),
+            167409079868000
+        );
+
+        let categories = Categories::default();
+        let part = Part::parse("{x=787,m=2655,a=1222,s=2876}", &categories).unwrap();
+        let containing: Vec<_> = boxes.iter().filter(|b| b.ranges.contains(&part)).collect();
+        assert_eq!(containing.len(), 1);
+        let path: Vec<_> = containing[0]
+            .path
+            .iter()
+            .map(|s| (s.workflow.as_str(), s.rule))
+            .collect();
+        assert_eq!(
+            path,
+            [
+                ("in", None),
+                ("qqz", Some(0)),
+                ("qs", None),
+                ("lnx", Some(0))
+            ]
+        );
+
+        // Rejected in the example
+        let part = Part::parse("{x=1679,m=44,a=2067,s=496}", &categories).unwrap();
+        assert!(!boxes.iter().any(|b| b.ranges.contains(&part)));
+    }
+
+    #[test]
+    fn undefined_target() {
+        let workflows: HashMap<String, Workflow> = ["in{x<2000:px,A}", "px{R}", "qq{m>5:zz,A}"]
+            .into_iter()
+            .map(|l| Workflow::parse(l, &Categories::default()).unwrap())
+            .map(|w| (w.name.clone(), w))
+            .collect();
+        let ranges = Ranges::filled(4, RATINGS);
+        assert_eq!(
+            workflows["in"]
+                .eval_range(&workflows, ranges.clone())
+                .unwrap(),
+            2001 * 4000usize.pow(3)
+        );
+        let error = workflows["qq"]
+            .accepted_boxes(&workflows, ranges)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "No workflow named zz");
+    }
+}
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Solution;
+use network::{Network, Pulse};
+
+mod network;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u8 {
+        20
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    let mut network = Network::from_str(input)?;
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..1000 {
+        network.push_button(|s| match s.pulse {
+            Pulse::Low => low += 1,
+            Pulse::High => high += 1,
+        });
+    }
+    Ok(low * high)
+}
+
+/// How many times part two presses the button waiting for every counter to fire
+const MAX_PRESSES: usize = 100_000;
+
+/// `rx` is fed by a single conjunction, which only sends a low pulse once all
+/// of its inputs have last sent a high pulse. Each of those inputs is the end
+/// of an independent counter that fires on a fixed cycle, so find each cycle
+/// and take the LCM
+pub fn part_two(input: &str) -> Result<usize> {
+    let mut network = Network::from_str(input)?;
+    let rx = network.get("rx").context("Network has no rx module")?;
+    let [feeder] = network.inputs(rx) else {
+        return Err(anyhow!("Expected rx to have exactly one input"));
+    };
+    let feeder = *feeder;
+    if !network.module(feeder).is_conjunction() {
+        return Err(anyhow!(
+            "Expected rx to be fed by a conjunction, not {}",
+            network.module(feeder).name
+        ));
+    }
+
+    let inputs = network.inputs(feeder).to_vec();
+    let mut cycles: Vec<Option<usize>> = vec![None; inputs.len()];
+    for presses in 1..=MAX_PRESSES {
+        network.push_button(|s| {
+            if s.to == feeder && s.pulse == Pulse::High {
+                let slot = inputs.iter().position(|&i| i == s.from).expect("is input");
+                cycles[slot].get_or_insert(presses);
+            }
+        });
+        if cycles.iter().all(Option::is_some) {
+            break;
+        }
+    }
+
+    let silent: Vec<&str> = inputs
+        .iter()
+        .zip(&cycles)
+        .filter(|(_, cycle)| cycle.is_none())
+        .map(|(&i, _)| network.module(i).name.as_str())
+        .collect();
+    if !silent.is_empty() {
+        return Err(anyhow!(
+            "{} never sent a high pulse to {} in {MAX_PRESSES} presses",
+            silent.join(", "),
+            network.module(feeder).name
+        ));
+    }
+    cycles
+        .into_iter()
+        .flatten()
+        .try_fold(1, lcm)
+        .context("The counter cycles line up after too many presses to count")
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let temp = b;
+        b = a % b;
+        a = temp;
+    }
+    a
+}
+
+/// None if the LCM is too big for a usize
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT_1: &str = {
+        "broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a"
+    };
+
+    const INPUT_2: &str = {
+        "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output"
+    };
+
+    /// Two binary counters whose inverters first send a high pulse to `fin` on
+    /// presses 3 and 7
+    const COUNTERS: &str = {
+        "broadcaster -> a1, b1
+%a1 -> a2, ca
+%a2 -> ca
+&ca -> ia
+&ia -> fin
+%b1 -> b2, cb
+%b2 -> b3, cb
+%b3 -> cb
+&cb -> ib
+&ib -> fin
+&fin -> rx"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT_1).unwrap(), 32000000);
+        assert_eq!(part_one(INPUT_2).unwrap(), 11687500);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(COUNTERS).unwrap(), 21);
+    }
+
+    #[test]
+    fn part_two_silent_input() {
+        assert_eq!(
+            part_two("broadcaster -> fin\n&fin -> rx")
+                .unwrap_err()
+                .to_string(),
+            "broadcaster never sent a high pulse to fin in 100000 presses"
+        );
+    }
+
+    #[test]
+    fn lcm_overflow() {
+        assert_eq!(lcm(4, 6), Some(12));
+        // The product overflows even though the LCM fits
+        assert_eq!(lcm(usize::MAX / 3, usize::MAX / 5), Some(usize::MAX));
+        assert_eq!(lcm(usize::MAX / 2, 3), None);
+    }
+
+    #[test]
+    fn part_two_needs_rx() {
+        assert!(part_two(INPUT_1).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+fn main() -> anyhow::Result<()> {
+    aoc_core::solve_from_args(&day20::Day20)
+}
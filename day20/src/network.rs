@@ -0,0 +1,230 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop {
+        on: bool,
+    },
+    /// Remembers the last pulse from each input, indexed the same as `inputs`
+    Conjunction {
+        memory: Vec<Pulse>,
+    },
+    /// Only receives pulses, like `output` or `rx`
+    Sink,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Module {
+    pub name: String,
+    kind: ModuleKind,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+}
+
+impl Module {
+    /// Handles a pulse from the input at `from`, returning the pulse to send
+    /// to every output, if any
+    fn receive(&mut self, from: usize, pulse: Pulse) -> Option<Pulse> {
+        match &mut self.kind {
+            ModuleKind::Broadcaster => Some(pulse),
+            ModuleKind::FlipFlop { .. } if pulse == Pulse::High => None,
+            ModuleKind::FlipFlop { on } => {
+                *on = !*on;
+                Some(if *on { Pulse::High } else { Pulse::Low })
+            }
+            ModuleKind::Conjunction { memory } => {
+                let slot = self
+                    .inputs
+                    .iter()
+                    .position(|&i| i == from)
+                    .expect("pulses only come from inputs");
+                memory[slot] = pulse;
+                if memory.iter().all(|&p| p == Pulse::High) {
+                    Some(Pulse::Low)
+                } else {
+                    Some(Pulse::High)
+                }
+            }
+            ModuleKind::Sink => None,
+        }
+    }
+
+    pub fn is_conjunction(&self) -> bool {
+        matches!(self.kind, ModuleKind::Conjunction { .. })
+    }
+}
+
+/// A pulse in flight from one module to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal {
+    pub from: usize,
+    pub to: usize,
+    pub pulse: Pulse,
+}
+
+/// Index used as the sender of the button's pulse to the broadcaster
+pub const BUTTON: usize = usize::MAX;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    modules: Vec<Module>,
+    names: HashMap<String, usize>,
+    broadcaster: usize,
+}
+
+impl Network {
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn module(&self, index: usize) -> &Module {
+        &self.modules[index]
+    }
+
+    pub fn inputs(&self, index: usize) -> &[usize] {
+        &self.modules[index].inputs
+    }
+
+    /// Pushes the button once, calling `observe` for every pulse sent
+    /// (including the button's own) in the order they are processed
+    pub fn push_button(&mut self, mut observe: impl FnMut(Signal)) {
+        let mut queue = VecDeque::new();
+        queue.push_back(Signal {
+            from: BUTTON,
+            to: self.broadcaster,
+            pulse: Pulse::Low,
+        });
+        while let Some(signal) = queue.pop_front() {
+            observe(signal);
+            let module = &mut self.modules[signal.to];
+            if let Some(pulse) = module.receive(signal.from, signal.pulse) {
+                queue.extend(module.outputs.iter().map(|&to| Signal {
+                    from: signal.to,
+                    to,
+                    pulse,
+                }));
+            }
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modules = Vec::new();
+        let mut names = HashMap::new();
+        let mut targets = Vec::new();
+
+        for l in s.lines() {
+            let (module, outputs) = l
+                .split_once("->")
+                .context(format!("Module {l} is missing '->'"))?;
+            let module = module.trim();
+            let (kind, name) = match module.chars().next() {
+                Some('%') => (ModuleKind::FlipFlop { on: false }, &module[1..]),
+                Some('&') => (ModuleKind::Conjunction { memory: Vec::new() }, &module[1..]),
+                _ if module == "broadcaster" => (ModuleKind::Broadcaster, module),
+                _ => return Err(anyhow!("Unknown module type in '{l}'")),
+            };
+            if names.insert(name.to_owned(), modules.len()).is_some() {
+                return Err(anyhow!("Module {name} is defined more than once"));
+            }
+            modules.push(Module {
+                name: name.to_owned(),
+                kind,
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+            });
+            targets.push(outputs);
+        }
+
+        for (i, outputs) in targets.into_iter().enumerate() {
+            for target in outputs.split(',').map(str::trim) {
+                let to = *names.entry(target.to_owned()).or_insert_with(|| {
+                    modules.push(Module {
+                        name: target.to_owned(),
+                        kind: ModuleKind::Sink,
+                        inputs: Vec::new(),
+                        outputs: Vec::new(),
+                    });
+                    modules.len() - 1
+                });
+                modules[i].outputs.push(to);
+                modules[to].inputs.push(i);
+            }
+        }
+
+        for module in modules.iter_mut() {
+            if let ModuleKind::Conjunction { memory } = &mut module.kind {
+                *memory = vec![Pulse::Low; module.inputs.len()];
+            }
+        }
+
+        let broadcaster = *names
+            .get("broadcaster")
+            .context("Network has no broadcaster")?;
+        Ok(Self {
+            modules,
+            names,
+            broadcaster,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_network() {
+        let network = Network::from_str(
+            "broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output",
+        )
+        .unwrap();
+        let con = network.get("con").unwrap();
+        let a = network.get("a").unwrap();
+        let b = network.get("b").unwrap();
+        assert!(network.module(con).is_conjunction());
+        assert_eq!(network.inputs(con), &[a, b]);
+        assert!(network.get("output").is_some());
+    }
+
+    #[test]
+    fn flip_flop_toggles() {
+        let mut network = Network::from_str("broadcaster -> a\n%a -> output").unwrap();
+        let output = network.get("output").unwrap();
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            network.push_button(|s| {
+                if s.to == output {
+                    received.push(s.pulse)
+                }
+            });
+        }
+        assert_eq!(received, vec![Pulse::High, Pulse::Low, Pulse::High]);
+    }
+
+    #[test]
+    fn invalid_networks() {
+        assert!(Network::from_str("%a -> b").is_err());
+        assert!(Network::from_str("broadcaster -> a\n%a -> b\n&a -> b").is_err());
+        assert!(Network::from_str("broadcaster a").is_err());
+    }
+}
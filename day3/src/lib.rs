@@ -0,0 +1,55 @@
+use aoc_core::Solution;
+
+use schematic::Schematic;
+
+mod schematic;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> u32 {
+    let schematic = Schematic::new(input);
+    schematic.part_numbers().sum()
+}
+
+pub fn part_two(input: &str) -> u32 {
+    let schematic = Schematic::new(input);
+    schematic.gears().map(|g| g.ratio()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(TEST_INPUT), 4361);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(TEST_INPUT), 467835);
+    }
+}
@@ -10,13 +10,12 @@ impl<'a> Schematic<'a> {
         Schematic {
             raw: input
                 .lines()
-                .map(|l| {
+                .inspect(|l| {
                     if let Some(exp) = line_length {
                         assert_eq!(l.len(), exp);
                     } else {
                         line_length = Some(l.len());
                     }
-                    l
                 })
                 .collect(),
             width: line_length.unwrap(),
@@ -1,5 +1,7 @@
-use anyhow::anyhow;
-use std::{num::ParseIntError, str::FromStr};
+use anyhow::{anyhow, Result};
+use std::{num::ParseIntError, ops::Range, str::FromStr};
+
+use crate::chain::{self, Categories};
 
 #[derive(Debug)]
 pub struct Almanac {
@@ -30,22 +32,66 @@ impl FromStr for Almanac {
 }
 
 impl Almanac {
-    pub fn locations(&self) -> impl Iterator<Item = u64> + '_ {
-        self.seeds.iter().map(|s| {
-            let mut d = *s;
-            for map in &self.maps[..] {
-                d = map.get(d);
-            }
-            d
-        })
+    fn chain(&self, from: &str, to: &str) -> Result<Vec<&Map>> {
+        chain::resolve(&self.maps, |m| &m.categories, from, to)
+    }
+
+    pub fn locations(&self) -> Result<impl Iterator<Item = u64> + '_> {
+        let chain = self.chain("seed", "location")?;
+        Ok(self
+            .seeds
+            .iter()
+            .map(move |&s| chain.iter().fold(s, |d, map| map.get(d))))
+    }
+
+    /// Converts a number in the `from` category, like `soil`, to the `to`
+    /// category by following the maps between them
+    pub fn convert(&self, value: u64, from: &str, to: &str) -> Result<u64> {
+        Ok(self
+            .chain(from, to)?
+            .iter()
+            .fold(value, |d, map| map.get(d)))
+    }
+
+    /// Every seed number that ends up at `location`, whether or not it is one
+    /// of the almanac's seeds. Fails if any map has overlapping ranges, since
+    /// then there's no single answer for which range a number goes through.
+    pub fn seeds_for(&self, location: u64) -> Result<Vec<u64>> {
+        let mut values = vec![location];
+        for map in self.chain("seed", "location")?.into_iter().rev() {
+            map.validate()?;
+            values = values.into_iter().flat_map(|v| map.sources(v)).collect();
+        }
+        values.sort_unstable();
+        values.dedup();
+        Ok(values)
+    }
+
+    /// Whether any of the almanac's seeds ends up at `location`
+    pub fn is_reachable(&self, location: u64) -> Result<bool> {
+        Ok(self
+            .seeds_for(location)?
+            .iter()
+            .any(|s| self.seeds.contains(s)))
     }
 }
 
 #[derive(Debug)]
 struct Map {
-    title: String,
+    categories: Categories,
     /// (dest, source, length)
     ranges: Box<[(u64, u64, u64)]>,
+    /// Why the map can't be reversed, worked out once when it's made
+    overlap: Option<Overlap>,
+}
+
+/// Two ranges on the same side of a map that share some numbers
+#[derive(Debug)]
+struct Overlap {
+    /// "source" or "destination"
+    side: &'static str,
+    a: Range<u64>,
+    b: Range<u64>,
 }
 
 impl FromStr for Map {
@@ -53,7 +99,7 @@ impl FromStr for Map {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let title = lines.next().ok_or(anyhow!("Map missing title"))?.to_owned();
+        let categories = lines.next().ok_or(anyhow!("Map missing title"))?.parse()?;
         let ranges: Box<[(u64, u64, u64)]> = lines
             .map(|l| {
                 let nums: Vec<_> = l
@@ -71,11 +117,34 @@ impl FromStr for Map {
             })
             .collect::<Result<Box<[(u64, u64, u64)]>, anyhow::Error>>()?;
 
-        Ok(Map { title, ranges })
+        Ok(Map::new(categories, ranges))
     }
 }
 
 impl Map {
+    fn new(categories: Categories, ranges: Box<[(u64, u64, u64)]>) -> Self {
+        let dests = ranges.iter().map(|r| (r.0, r.2));
+        let sources = ranges.iter().map(|r| (r.1, r.2));
+        let overlap = [
+            ("destination", overlap(dests)),
+            ("source", overlap(sources)),
+        ]
+        .into_iter()
+        .find_map(|(side, ranges)| {
+            let ((a, a_len), (b, b_len)) = ranges?;
+            Some(Overlap {
+                side,
+                a: a..a + a_len,
+                b: b..b + b_len,
+            })
+        });
+        Self {
+            categories,
+            ranges,
+            overlap,
+        }
+    }
+
     /// Takes a source number and converts it to the destination number
     /// If the number is not in any of the source ranges, the destination
     /// number is the same as the source.
@@ -89,6 +158,45 @@ impl Map {
         }
         source
     }
+
+    /// The reverse of `get`: every source number that converts to `dest`.
+    /// There can be two, one through a range and the other passing straight
+    /// through, or none if `dest` is only passed through but is also covered by
+    /// a source range.
+    fn sources(&self, dest: u64) -> Vec<u64> {
+        let mut sources: Vec<u64> = self
+            .ranges
+            .iter()
+            .filter(|r| dest >= r.0 && dest < r.0 + r.2)
+            .map(|r| r.1 + (dest - r.0))
+            .collect();
+        if !self.ranges.iter().any(|r| dest >= r.1 && dest < r.1 + r.2) {
+            sources.push(dest);
+        }
+        sources
+    }
+
+    /// Checks that no two source ranges overlap, and no two destination
+    /// ranges overlap, going by what `new` found
+    fn validate(&self) -> Result<()> {
+        match &self.overlap {
+            Some(Overlap { side, a, b }) => Err(anyhow!(
+                "{} has overlapping {side} ranges {a:?} and {b:?}",
+                self.categories
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Finds a pair of overlapping (start, length) ranges
+fn overlap(ranges: impl Iterator<Item = (u64, u64)>) -> Option<((u64, u64), (u64, u64))> {
+    let mut ranges: Vec<_> = ranges.collect();
+    ranges.sort_unstable();
+    ranges
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .find(|((a, a_len), (b, _))| *b < a + a_len)
 }
 
 #[cfg(test)]
@@ -97,13 +205,72 @@ mod tests {
 
     #[test]
     fn map_get() {
-        let map = Map {
-            title: "test_to_test2".to_owned(),
-            ranges: vec![(50, 98, 2)].into_boxed_slice(),
-        };
+        let map = Map::new(
+            "test-to-test2 map:".parse().unwrap(),
+            vec![(50, 98, 2)].into_boxed_slice(),
+        );
         assert_eq!(map.get(97), 97);
         assert_eq!(map.get(98), 50);
         assert_eq!(map.get(99), 51);
         assert_eq!(map.get(100), 100);
     }
+
+    #[test]
+    fn map_sources() {
+        let map = Map::new(
+            "test-to-test2 map:".parse().unwrap(),
+            vec![(50, 98, 2), (52, 50, 48)].into_boxed_slice(),
+        );
+        assert_eq!(map.sources(50), vec![98]);
+        assert_eq!(map.sources(55), vec![53]);
+        assert_eq!(map.sources(10), vec![10]);
+        // 98 and 99 are converted, so nothing passes straight through to them
+        assert_eq!(map.sources(98), vec![96]);
+        assert!(map.sources(100).contains(&100));
+    }
+
+    #[test]
+    fn map_validate() {
+        let map = Map::new(
+            "test-to-test2 map:".parse().unwrap(),
+            vec![(50, 98, 2), (52, 50, 48)].into_boxed_slice(),
+        );
+        assert!(map.validate().is_ok());
+
+        let map = Map::new(
+            "test-to-test2 map:".parse().unwrap(),
+            vec![(0, 10, 5), (20, 14, 5)].into_boxed_slice(),
+        );
+        assert_eq!(
+            map.validate().unwrap_err().to_string(),
+            "test-to-test2 has overlapping source ranges 10..15 and 14..19"
+        );
+
+        let map = Map::new(
+            "test-to-test2 map:".parse().unwrap(),
+            vec![(0, 10, 5), (4, 20, 5)].into_boxed_slice(),
+        );
+        assert!(map.validate().is_err());
+    }
+
+    #[test]
+    fn reverse_lookup() {
+        let almanac: Almanac = crate::tests::BASIC_INPUT.parse().unwrap();
+        for (seed, location) in [(79, 82), (14, 43), (55, 86), (13, 35)] {
+            assert!(almanac.seeds_for(location).unwrap().contains(&seed));
+            assert!(almanac.is_reachable(location).unwrap());
+        }
+        assert!(!almanac.is_reachable(46).unwrap());
+    }
+
+    #[test]
+    fn convert_by_name() {
+        let almanac: Almanac = crate::tests::BASIC_INPUT.parse().unwrap();
+        // Seed 79 has soil 81, fertilizer 81 and water 81
+        assert_eq!(almanac.convert(81, "soil", "water").unwrap(), 81);
+        assert_eq!(almanac.convert(79, "seed", "location").unwrap(), 82);
+        // Seed 14, soil 14, fertilizer 53, water 49, light 42
+        assert_eq!(almanac.convert(14, "soil", "light").unwrap(), 42);
+        assert!(almanac.convert(14, "location", "seed").is_err());
+    }
 }
@@ -1,6 +1,8 @@
-use anyhow::anyhow;
+use anyhow::{anyhow, Result};
 use std::{num::ParseIntError, str::FromStr};
 
+use crate::chain::{self, Categories};
+
 #[derive(Debug)]
 pub struct Almanac2 {
     seeds: Box<[(u64, u64)]>,
@@ -31,22 +33,68 @@ impl FromStr for Almanac2 {
 }
 
 impl Almanac2 {
-    /// Could definately use some optimization. Very slow on the large ranges of input in test 2
-    pub fn locations(&self) -> impl Iterator<Item = u64> + '_ {
-        self.seeds.iter().flat_map(|&(rs, rl)| {
-            (rs..(rs + rl)).map(|s| {
-                let mut d = s;
-                for map in &self.maps[..] {
-                    d = map.get(d);
-                }
-                d
+    /// Every seed range pushed through all of the maps, split wherever a map
+    /// sends parts of it to different places
+    fn spans(&self) -> Result<Vec<Span>> {
+        let mut spans: Vec<Span> = self
+            .seeds
+            .iter()
+            .filter(|&&(_, len)| len > 0)
+            .map(|&(start, len)| Span {
+                seed: start,
+                value: start,
+                len,
+            })
+            .collect();
+        for map in chain::resolve(&self.maps, |m| &m.categories, "seed", "location")? {
+            spans = map.get_spans(spans);
+        }
+        Ok(spans)
+    }
+
+    /// Location ranges as (start, length) for all of the seed ranges
+    pub fn locations(&self) -> Result<impl Iterator<Item = (u64, u64)>> {
+        Ok(self.spans()?.into_iter().map(|s| (s.value, s.len)))
+    }
+
+    /// The location a single seed number ends up at, whether or not it is in
+    /// one of the almanac's seed ranges
+    pub fn location(&self, seed: u64) -> Result<u64> {
+        Ok(
+            chain::resolve(&self.maps, |m| &m.categories, "seed", "location")?
+                .iter()
+                .fold(seed, |d, map| map.get(d)),
+        )
+    }
+
+    /// Seed ranges as (start, length) that end up somewhere in the location
+    /// range (start, length)
+    pub fn seeds_for(&self, (start, len): (u64, u64)) -> Result<Vec<(u64, u64)>> {
+        let mut seeds: Vec<(u64, u64)> = self
+            .spans()?
+            .into_iter()
+            .filter_map(|s| {
+                let first = s.value.max(start);
+                let end = (s.value + s.len).min(start + len);
+                (first < end).then(|| (s.seed + (first - s.value), end - first))
             })
-        })
+            .collect();
+        seeds.sort_unstable();
+        Ok(seeds)
     }
 }
 
+/// A run of consecutive seeds and the consecutive values they currently map to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    seed: u64,
+    value: u64,
+    len: u64,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 struct Map {
+    categories: Categories,
     /// (dest, source, length)
     ranges: Box<[(u64, u64, u64)]>,
 }
@@ -56,7 +104,7 @@ impl FromStr for Map {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let _ = lines.next().ok_or(anyhow!("Map missing title"))?.to_owned();
+        let categories = lines.next().ok_or(anyhow!("Map missing title"))?.parse()?;
         let ranges: Box<[(u64, u64, u64)]> = lines
             .map(|l| {
                 let nums: Vec<_> = l
@@ -74,7 +122,7 @@ impl FromStr for Map {
             })
             .collect::<Result<Box<[(u64, u64, u64)]>, anyhow::Error>>()?;
 
-        Ok(Map { ranges })
+        Ok(Map { categories, ranges })
     }
 }
 
@@ -92,6 +140,45 @@ impl Map {
         }
         source
     }
+
+    /// Same as `get`, but for whole spans at once. Each span is split against
+    /// the source ranges, with the overlapping parts moved to the destination
+    /// and anything left over passed through unchanged.
+    fn get_spans(&self, mut unmapped: Vec<Span>) -> Vec<Span> {
+        let mut mapped = Vec::with_capacity(unmapped.len());
+        for &(dest, source, len) in self.ranges.iter() {
+            let mut rest = Vec::with_capacity(unmapped.len());
+            for span in unmapped {
+                let first = span.value.max(source);
+                let end = (span.value + span.len).min(source + len);
+                if first >= end {
+                    rest.push(span);
+                    continue;
+                }
+                mapped.push(Span {
+                    seed: span.seed + (first - span.value),
+                    value: dest + (first - source),
+                    len: end - first,
+                });
+                if span.value < first {
+                    rest.push(Span {
+                        len: first - span.value,
+                        ..span
+                    });
+                }
+                if end < span.value + span.len {
+                    rest.push(Span {
+                        seed: span.seed + (end - span.value),
+                        value: end,
+                        len: span.value + span.len - end,
+                    });
+                }
+            }
+            unmapped = rest;
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +188,7 @@ mod tests {
     #[test]
     fn map_get() {
         let map = Map {
+            categories: "test-to-test2 map:".parse().unwrap(),
             ranges: vec![(50, 98, 2)].into_boxed_slice(),
         };
         assert_eq!(map.get(97), 97);
@@ -108,4 +196,75 @@ mod tests {
         assert_eq!(map.get(99), 51);
         assert_eq!(map.get(100), 100);
     }
+
+    #[test]
+    fn map_get_spans() {
+        let map = Map {
+            categories: "test-to-test2 map:".parse().unwrap(),
+            ranges: vec![(50, 98, 2), (52, 50, 48)].into_boxed_slice(),
+        };
+        let span = |seed, value, len| Span { seed, value, len };
+        let mut spans = map.get_spans(vec![span(0, 40, 70), span(100, 200, 5)]);
+        spans.sort_by_key(|s| s.seed);
+        assert_eq!(
+            spans,
+            vec![
+                span(0, 40, 10),
+                span(10, 52, 48),
+                span(58, 50, 2),
+                span(60, 100, 10),
+                span(100, 200, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_match_seeds() {
+        let almanac: Almanac2 = crate::tests::BASIC_INPUT.parse().unwrap();
+        for (seed, location) in almanac
+            .spans()
+            .unwrap()
+            .into_iter()
+            .flat_map(|s| (0..s.len).map(move |i| (s.seed + i, s.value + i)))
+        {
+            assert_eq!(almanac.location(seed).unwrap(), location);
+        }
+    }
+
+    #[test]
+    fn empty_seed_range() {
+        let input = crate::tests::BASIC_INPUT.replace("79 14 55 13", "55 13 0 0");
+        let almanac: Almanac2 = input.parse().unwrap();
+        let spans = almanac.spans().unwrap();
+        assert!(spans.iter().all(|s| (55..68).contains(&s.seed)));
+        assert_eq!(spans.iter().map(|s| s.len).sum::<u64>(), 13);
+        assert_eq!(crate::part_two(&input), 56);
+    }
+
+    #[test]
+    fn seeds_for_locations() {
+        let almanac: Almanac2 = crate::tests::BASIC_INPUT.parse().unwrap();
+        // Seed 82 is the only one that reaches location 46
+        assert_eq!(almanac.seeds_for((46, 1)).unwrap(), vec![(82, 1)]);
+        assert!(almanac.seeds_for((0, 46)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn maps_out_of_order() {
+        let input = crate::tests::BASIC_INPUT;
+        let (seeds, maps) = input.split_once("\n\n").unwrap();
+        let mut maps: Vec<_> = maps.split("\n\n").collect();
+        maps.reverse();
+        let shuffled: Almanac2 = format!("{seeds}\n\n{}", maps.join("\n\n")).parse().unwrap();
+        let mut locations: Vec<_> = shuffled.locations().unwrap().collect();
+        let mut expected: Vec<_> = input
+            .parse::<Almanac2>()
+            .unwrap()
+            .locations()
+            .unwrap()
+            .collect();
+        locations.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(locations, expected);
+    }
 }
@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Context, Result};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+/// The two categories a map converts between, from a title like
+/// `seed-to-soil map:`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Categories {
+    pub from: String,
+    pub to: String,
+}
+
+impl FromStr for Categories {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (from, to) = s
+            .strip_suffix(" map:")
+            .and_then(|t| t.split_once("-to-"))
+            .context(format!("Map title '{s}' isn't of the form 'x-to-y map:'"))?;
+        Ok(Self {
+            from: from.to_owned(),
+            to: to.to_owned(),
+        })
+    }
+}
+
+impl Display for Categories {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{}", self.from, self.to)
+    }
+}
+
+/// Finds the maps to go through, in order, to convert from one category to
+/// another. Fails if a category along the way has no map or more than one
+/// map out of it, or if the maps loop back on themselves before reaching `to`.
+pub fn resolve<'a, M>(
+    maps: &'a [M],
+    categories: impl Fn(&M) -> &Categories,
+    from: &str,
+    to: &str,
+) -> Result<Vec<&'a M>> {
+    let mut chain = Vec::new();
+    let mut seen = HashSet::from([from]);
+    let mut current = from;
+    while current != to {
+        let mut next = maps.iter().filter(|m| categories(m).from == current);
+        let map = next.next().ok_or(anyhow!(
+            "No map from {current} while converting {from} to {to}"
+        ))?;
+        if next.next().is_some() {
+            return Err(anyhow!("More than one map from {current}"));
+        }
+        current = &categories(map).to;
+        if !seen.insert(current) {
+            return Err(anyhow!(
+                "Maps loop back to {current} while converting {from} to {to}"
+            ));
+        }
+        chain.push(map);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maps(titles: &[&str]) -> Vec<Categories> {
+        titles.iter().map(|t| t.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn parse_categories() {
+        let c: Categories = "seed-to-soil map:".parse().unwrap();
+        assert_eq!(c.from, "seed");
+        assert_eq!(c.to, "soil");
+        assert!("seed-soil map:".parse::<Categories>().is_err());
+        assert!("seed-to-soil".parse::<Categories>().is_err());
+    }
+
+    #[test]
+    fn resolve_out_of_order() {
+        let maps = maps(&[
+            "water-to-light map:",
+            "seed-to-soil map:",
+            "soil-to-water map:",
+        ]);
+        let chain = resolve(&maps, |m| m, "seed", "light").unwrap();
+        assert_eq!(chain, vec![&maps[1], &maps[2], &maps[0]]);
+        assert_eq!(
+            resolve(&maps, |m| m, "soil", "water").unwrap(),
+            vec![&maps[2]]
+        );
+        assert!(resolve(&maps, |m| m, "soil", "soil").unwrap().is_empty());
+    }
+
+    #[test]
+    fn resolve_errors() {
+        let broken = maps(&["seed-to-soil map:", "water-to-light map:"]);
+        assert_eq!(
+            resolve(&broken, |m| m, "seed", "light")
+                .unwrap_err()
+                .to_string(),
+            "No map from soil while converting seed to light"
+        );
+
+        let cycle = maps(&["seed-to-soil map:", "soil-to-seed map:"]);
+        assert_eq!(
+            resolve(&cycle, |m| m, "seed", "light")
+                .unwrap_err()
+                .to_string(),
+            "Maps loop back to seed while converting seed to light"
+        );
+
+        let ambiguous = maps(&["seed-to-soil map:", "seed-to-water map:"]);
+        assert!(resolve(&ambiguous, |m| m, "seed", "water").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use aoc_core::Solution;
+
+use almanac::Almanac;
+use almanac2::Almanac2;
+
+pub mod almanac;
+pub mod almanac2;
+mod chain;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+    fn part_one(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_one(input).to_string())
+    }
+    fn part_two(&self, input: &str) -> anyhow::Result<String> {
+        Ok(part_two(input).to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> u64 {
+    let almanac: Almanac = input.parse().unwrap();
+
+    almanac.locations().unwrap().min().unwrap()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    let a2: Almanac2 = input.parse().unwrap();
+
+    a2.locations()
+        .unwrap()
+        .map(|(start, _)| start)
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub const BASIC_INPUT: &str = {
+        "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4"
+    };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(BASIC_INPUT), 35);
+    }
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(BASIC_INPUT), 46);
+    }
+}
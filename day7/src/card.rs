@@ -27,13 +27,13 @@ impl FromStr for Hand {
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some((HandKind::from_hand(self), self.0).cmp(&(HandKind::from_hand(other), other.0)))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).expect("always returns an order")
+        (HandKind::from_hand(self), self.0).cmp(&(HandKind::from_hand(other), other.0))
     }
 }
 
@@ -68,11 +68,7 @@ impl HandKind {
             match hand.0.iter().filter(|&&c| c == card).count() + jokers {
                 n if n == jokers => (),
                 5 => return Self::Five,
-                4 => {
-                    if kind > Self::Four {
-                        kind = Self::Four
-                    }
-                }
+                4 if kind > Self::Four => kind = Self::Four,
                 3 => match kind {
                     Self::High => kind = Self::Three,
                     Self::One if jokers == 0 => kind = Self::Full, // Otherwise would be 3
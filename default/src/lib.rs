@@ -0,0 +1,43 @@
+use anyhow::Result;
+use aoc_core::Solution;
+
+pub struct DayN;
+
+impl Solution for DayN {
+    fn day(&self) -> u8 {
+        0
+    }
+    fn part_one(&self, input: &str) -> Result<String> {
+        part_one(input).map(|a| a.to_string())
+    }
+    fn part_two(&self, input: &str) -> Result<String> {
+        part_two(input).map(|a| a.to_string())
+    }
+}
+
+pub fn part_one(input: &str) -> Result<usize> {
+    _ = input;
+    Ok(0)
+}
+
+pub fn part_two(input: &str) -> Result<usize> {
+    _ = input;
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = { "" };
+
+    #[test]
+    fn part_one_basic() {
+        assert_eq!(part_one(INPUT).unwrap(), 0);
+    }
+
+    #[test]
+    fn part_two_basic() {
+        assert_eq!(part_two(INPUT).unwrap(), 0);
+    }
+}